@@ -3,6 +3,7 @@ use std::thread::{self};
 use crate::{
     databases::Databases,
     datasets::AllDatasets,
+    io::Checkpoint,
     states::States,
     structs::{Date, Height},
     utils::{log, time},
@@ -15,6 +16,7 @@ pub struct ExportedData<'a> {
     pub date: Date,
     pub height: Height,
     pub states: Option<&'a States>,
+    pub record_databases: bool,
     pub exit: Exit,
 }
 
@@ -23,6 +25,7 @@ pub fn export(
         databases,
         datasets,
         states,
+        record_databases,
         height,
         date,
         exit,
@@ -38,18 +41,37 @@ pub fn export(
     log("Exporting...");
 
     time("Total save time", || -> color_eyre::Result<()> {
+        let checkpoint = Checkpoint::begin(height, date)?;
+
         time("Datasets saved", || datasets.export())?;
 
-        thread::scope(|s| {
-            if let Some(databases) = databases {
-                s.spawn(|| time("Databases saved", || databases.export(height, date)));
-            }
+        let (databases, states) = thread::scope(|s| {
+            let databases = databases.map(|databases| {
+                s.spawn(|| {
+                    time("Databases saved", || {
+                        databases.export(height, date, record_databases)
+                    })
+                })
+            });
+
+            let states = states.map(|states| s.spawn(|| time("States saved", || states.export())));
 
-            if let Some(states) = states {
-                s.spawn(|| time("States saved", || states.export()));
-            }
+            (
+                databases.map(|handle| handle.join().unwrap()),
+                states.map(|handle| handle.join().unwrap()),
+            )
         });
 
+        let databases = databases.transpose()?;
+        states.transpose()?;
+
+        time("Checkpoint committed", || checkpoint.commit())?;
+
+        // Interrupting past this point is fine, the write-ahead logs are replayed on open
+        if let Some(databases) = databases {
+            time("Databases committed", || Databases::commit(databases))?;
+        }
+
         Ok(())
     })?;
 
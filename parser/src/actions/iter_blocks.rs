@@ -10,10 +10,10 @@ use crate::{
     create_rpc,
     databases::Databases,
     datasets::{AllDatasets, ComputeData},
-    io::OUTPUTS_FOLDER_PATH,
+    io::{Checkpoint, OUTPUTS_FOLDER_PATH},
     states::{AddressCohortsDurableStates, States, UTXOCohortsDurableStates},
-    structs::{DateData, MapKey, Timestamp},
-    utils::{generate_allocation_files, log, time},
+    structs::{DateData, MapDependencies, MapKey, Timestamp},
+    utils::{generate_allocation_files, log, time, RamBudget},
     Config, Exit, Height,
 };
 
@@ -25,8 +25,19 @@ pub fn iter_blocks(
 ) -> color_eyre::Result<()> {
     log("Starting...");
 
+    Checkpoint::recover()?;
+
+    if let Some(glob) = config.recompute() {
+        MapDependencies::start_recomputing(glob)?;
+    }
+
     let mut datasets = AllDatasets::import(config)?;
 
+    if config.recompute().is_some() {
+        MapDependencies::stop_recomputing();
+        config.disable_recompute();
+    }
+
     log("Imported datasets");
 
     let mut databases = Databases::import();
@@ -62,6 +73,8 @@ pub fn iter_blocks(
 
     let mut block_iter = block_receiver.iter();
 
+    let mut ram_budget = config.max_ram.map(RamBudget::new);
+
     'parsing: loop {
         let instant = Instant::now();
 
@@ -127,7 +140,7 @@ pub fn iter_blocks(
 
                     let is_date_last_block = next_block_date
                         // Do NOT change `blocks_loop_date` to `current_block_date` !!!
-                        .map_or(true, |next_block_date| blocks_loop_date < next_block_date);
+                        .is_none_or(|next_block_date| blocks_loop_date < next_block_date);
 
                     processed_heights.insert(current_block_height);
 
@@ -180,12 +193,17 @@ pub fn iter_blocks(
 
                         let is_check_point = next_block_date
                             .as_ref()
-                            .map_or(true, |date| date.is_first_of_month());
+                            .is_none_or(|date| date.is_first_of_month());
 
                         let ran_for_at_least_a_minute = instant.elapsed().as_secs() >= 60;
 
+                        let is_ram_budget_exceeded = ram_budget
+                            .as_ref()
+                            .is_some_and(|ram_budget| ram_budget.is_exceeded());
+
                         if (is_check_point && ran_for_at_least_a_minute)
                             || height.is_close_to_end(approx_block_count)
+                            || is_ram_budget_exceeded
                         {
                             break 'days;
                         }
@@ -229,9 +247,29 @@ pub fn iter_blocks(
                 date: blocks_loop_date.unwrap(),
                 height: last_height,
                 states: is_safe.then_some(&states),
+                record_databases: config.record_databases(),
                 exit: exit.clone(),
             })?;
 
+            if is_safe && config.background_compaction() {
+                databases.compact_in_background(&exit);
+            }
+
+            // Databases are already dropped by the safe export
+            // Everything else is rebuilt from the exported states and files when needed
+            if is_safe
+                && ram_budget
+                    .as_mut()
+                    .is_some_and(|ram_budget| ram_budget.flushed())
+            {
+                log("Still above the ram budget, evicting imported chunks and dropping durable states");
+
+                datasets.evict();
+
+                states.address_cohorts_durable_states.take();
+                states.utxo_cohorts_durable_states.take();
+            }
+
             if config.record_ram_usage() {
                 time("Exporing allocation files", || {
                     generate_allocation_files(&datasets, &databases, &states, last_height)
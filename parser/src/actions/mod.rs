@@ -2,8 +2,12 @@ mod export;
 mod iter_blocks;
 mod min_height;
 mod parse;
+mod snapshot;
+mod verify;
 
 pub use export::*;
 pub use iter_blocks::*;
 pub use min_height::*;
 pub use parse::*;
+pub use snapshot::*;
+pub use verify::*;
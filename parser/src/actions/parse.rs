@@ -16,11 +16,13 @@ use crate::{
     datasets::{AllDatasets, InsertData},
     states::{
         AddressCohortsInputStates, AddressCohortsOutputStates, AddressCohortsRealizedStates,
-        States, UTXOCohortsOneShotStates, UTXOCohortsSentStates,
+        States, UTXOCohortsAgingStates, UTXOCohortsOneShotStates, UTXOCohortsReceivedStates,
+        UTXOCohortsSentStates,
     },
     structs::{
-        Address, AddressData, AddressRealizedData, Amount, BlockData, BlockPath, Counter, Date,
-        EmptyAddressData, Height, PartialTxoutData, Price, SentData, Timestamp, TxData, TxoutIndex,
+        Address, AddressActivity, AddressData, AddressRealizedData, Amount, BlockData, BlockPath,
+        Counter, Date, EmptyAddressData, Height, PartialTxoutData, Price, ReceivedData, SentData,
+        Timestamp, TxData, TxoutIndex,
     },
 };
 
@@ -105,7 +107,7 @@ pub fn parse(
         .push(BlockData::new(height, block_price, timestamp));
 
     let mut block_path_to_sent_data: BTreeMap<BlockPath, SentData> = BTreeMap::default();
-    // let mut received_data: ReceivedData = ReceivedData::default();
+    let mut received_data = ReceivedData::default();
     let mut address_index_to_address_realized_data: BTreeMap<u32, AddressRealizedData> =
         BTreeMap::default();
 
@@ -166,7 +168,7 @@ pub fn parse(
         )
     });
 
-    block
+    let _ = block
         .txdata
         .iter()
         .enumerate()
@@ -232,6 +234,8 @@ pub fn parse(
 
                     last_block.receive(amount);
 
+                    received_data.receive(amount);
+
                     utxos.insert(vout, amount);
 
                     databases
@@ -550,6 +554,22 @@ pub fn parse(
         panic!("partial_txout_data_vec should've been fully consumed");
     }
 
+    states.supply_totals.receive(
+        states.date_data_vec.last_block().unwrap(),
+        received_data.volume,
+    );
+
+    block_path_to_sent_data
+        .iter()
+        .for_each(|(block_path, sent_data)| {
+            states.supply_totals.send(
+                states.date_data_vec.get_block_data(block_path).unwrap(),
+                sent_data.volume,
+            );
+        });
+
+    states.supply_totals.age(&states.date_data_vec, timestamp);
+
     txid_to_tx_data.into_iter().for_each(|(txid, tx_data)| {
         if let Some(tx_data) = tx_data {
             if tx_data.is_empty() {
@@ -562,12 +582,14 @@ pub fn parse(
 
     let mut utxo_cohorts_sent_states = UTXOCohortsSentStates::default();
     let mut utxo_cohorts_one_shot_states = UTXOCohortsOneShotStates::default();
-    // let mut utxo_cohorts_received_states = UTXOCohortsReceivedStates::default();
+    let mut utxo_cohorts_received_states = UTXOCohortsReceivedStates::default();
+    let mut utxo_cohorts_aging_states = UTXOCohortsAgingStates::default();
 
     let mut address_cohorts_input_states = None;
     let mut address_cohorts_one_shot_states = None;
     let mut address_cohorts_output_states = None;
     let mut address_cohorts_realized_states = None;
+    let mut address_activity = AddressActivity::default();
 
     // log("Starting heavy work...");
 
@@ -620,6 +642,16 @@ pub fn parse(
                     });
             }
 
+            if datasets.utxo.needs_aging_states(height, date) {
+                if let Some(previous_last_block_data) = previous_last_block_data {
+                    utxo_cohorts_aging_states.compute(
+                        &states.date_data_vec,
+                        states.date_data_vec.last_block().unwrap(),
+                        previous_last_block_data,
+                    );
+                }
+            }
+
             if datasets.utxo.needs_one_shot_states(height, date) {
                 utxo_cohorts_one_shot_states = states
                     .utxo_cohorts_durable_states
@@ -636,10 +668,9 @@ pub fn parse(
             }
         });
 
-        // scope.spawn(|| {
-        //     utxo_cohorts_received_states
-        //         .compute(&states.date_data_vec, block_path_to_received_data);
-        // });
+        if datasets.utxo.needs_received_states(height, date) {
+            utxo_cohorts_received_states.compute(height, &received_data);
+        }
 
         if datasets.utxo.needs_sent_states(height, date) {
             scope.spawn(|| {
@@ -680,6 +711,12 @@ pub fn parse(
                                 panic!();
                             });
 
+                        address_activity.iterate(
+                            *address_index,
+                            address_realized_data,
+                            current_address_data,
+                        );
+
                         if !address_realized_data.initial_address_data.is_empty() {
                             // Realized == previous amount
                             // If a whale sent all its sats to another address at a loss, it's the whale that realized the loss not the now empty adress
@@ -747,11 +784,13 @@ pub fn parse(
     }
 
     datasets.insert(InsertData {
+        address_activity: &address_activity,
         address_cohorts_input_states: &address_cohorts_input_states,
         block_size,
         block_vbytes,
         block_weight,
         address_cohorts_one_shot_states: &address_cohorts_one_shot_states,
+        address_cohorts_output_states: &address_cohorts_output_states,
         address_cohorts_realized_states: &address_cohorts_realized_states,
         block_interval,
         block_price,
@@ -771,7 +810,9 @@ pub fn parse(
         states,
         timestamp,
         transaction_count,
+        utxo_cohorts_aging_states: &utxo_cohorts_aging_states,
         utxo_cohorts_one_shot_states: &utxo_cohorts_one_shot_states,
+        utxo_cohorts_received_states: &utxo_cohorts_received_states,
         utxo_cohorts_sent_states: &utxo_cohorts_sent_states,
     });
 }
@@ -853,8 +894,7 @@ fn prepare_outputs(
             .for_each(|partial_tx_out_data| {
                 if let Some(partial_tx_out_data) = partial_tx_out_data {
                     let address_index_opt = address_to_address_index
-                        .unsafe_get(partial_tx_out_data.address.as_ref().unwrap())
-                        .cloned();
+                        .unsafe_get(partial_tx_out_data.address.as_ref().unwrap());
 
                     partial_tx_out_data.address_index_opt = address_index_opt;
                 }
@@ -900,7 +940,7 @@ fn prepare_inputs<'a>(
         .collect::<Vec<_>>();
 
     txid_to_tx_data.values_mut().rev().for_each(|tx_data_opt| {
-        *tx_data_opt = tx_datas.pop().unwrap().cloned();
+        *tx_data_opt = tx_datas.pop().unwrap();
     });
 
     let txout_index_to_amount_and_address_index = block
@@ -935,12 +975,12 @@ fn prepare_inputs<'a>(
                 // which is used later as input
                 .map(|amount| {
                     let address_index = compute_addresses.then(|| {
-                        *txout_index_to_address_index_db
+                        txout_index_to_address_index_db
                             .unsafe_get(&txout_index)
                             .unwrap()
                     });
 
-                    (txout_index, (*amount, address_index))
+                    (txout_index, (amount, address_index))
                 })
         })
         .collect::<BTreeMap<_, _>>();
@@ -997,13 +1037,13 @@ fn compute_address_index_to_address_data(
             } else if let Some(_address_data) =
                 address_index_to_address_data_db.unsafe_get_from_db(address_index)
             {
-                _address_data.clone_into(address_data);
+                *address_data = _address_data;
             } else {
                 let empty_address_data = address_index_to_empty_address_data_db
                     .unsafe_get_from_db(address_index)
                     .unwrap();
 
-                *address_data = AddressData::from_empty(empty_address_data);
+                *address_data = AddressData::from_empty(&empty_address_data);
             }
         });
 
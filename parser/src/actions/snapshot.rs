@@ -0,0 +1,362 @@
+use std::{
+    collections::BTreeMap,
+    fs::{self, File},
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::{Component, Path, PathBuf},
+};
+
+use bitcoin_hashes::{sha256, Hash, HashEngine};
+use biter::bitcoincore_rpc::RpcApi;
+use color_eyre::eyre::eyre;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    databases::WRITE_AHEAD_LOGS_FOLDER,
+    datasets::DATASETS_PATH,
+    io::{Checkpoint, OutputsLock, OUTPUTS_FOLDER_PATH},
+    states::States,
+    structs::{Date, Height, MapDependencies, MapKey},
+    utils::log,
+};
+
+/// Bumped when the layout of the archive changes
+const SNAPSHOT_VERSION: u32 = 1;
+const MANIFEST_FILE_NAME: &str = "snapshot.json";
+const STAGING_FOLDER_PATH: &str = "./snapshot";
+const ZSTD_LEVEL: i32 = 3;
+
+/// Written by biter in the outputs folder
+const BLK_INDEX_TO_BLK_RECAP_FILE_NAME: &str = "blk_index_to_blk_recap.json";
+
+/// Folders of the archive with the local path they're extracted to
+const ROOTS: [(&str, &str); 2] = [("out", OUTPUTS_FOLDER_PATH), ("datasets", DATASETS_PATH)];
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    version: u32,
+    parser_version: String,
+    height: Height,
+    date: Date,
+    block_hash: String,
+    /// Sha256 of every file, by path in the archive
+    checksums: BTreeMap<PathBuf, String>,
+}
+
+/// What's needed in the outputs folder to resume parsing, compaction leftovers and benches aren't
+fn outputs() -> [&'static str; 5] {
+    [
+        "databases",
+        "states",
+        WRITE_AHEAD_LOGS_FOLDER,
+        BLK_INDEX_TO_BLK_RECAP_FILE_NAME,
+        MapDependencies::FILE_NAME,
+    ]
+}
+
+/// Local paths included in a snapshot, by path in the archive
+fn targets() -> Vec<(PathBuf, PathBuf)> {
+    let (out, out_path) = ROOTS[0];
+    let (datasets, datasets_path) = ROOTS[1];
+
+    outputs()
+        .into_iter()
+        .map(|name| (Path::new(out).join(name), Path::new(out_path).join(name)))
+        .chain([(PathBuf::from(datasets), PathBuf::from(datasets_path))])
+        .collect()
+}
+
+///
+/// Archives (tar + zstd) everything needed to resume parsing from the last safe export.
+///
+/// Only the states of the last export are kept, so `height` can only be that one and is checked when given.
+/// Taking the lock ensures that the parser isn't running at the same time.
+/// The manifest, with the checksum of every file, is the last entry since the checksums are computed while archiving.
+///
+pub fn create_snapshot(
+    rpc: &biter::bitcoincore_rpc::Client,
+    path: &Path,
+    height: Option<u32>,
+    _lock: &OutputsLock,
+) -> color_eyre::Result<()> {
+    // Finishes or drops an interrupted export
+    Checkpoint::recover()?;
+
+    let states = States::import()?;
+
+    let (Some(date_data), Some(block_data)) = (
+        states.date_data_vec.last(),
+        states.date_data_vec.last_block(),
+    ) else {
+        return Err(eyre!("Nothing to snapshot, parse some blocks first"));
+    };
+
+    let date = date_data.date;
+    let last_height = block_data.height;
+
+    if let Some(height) = height
+        .map(Height::new)
+        .filter(|height| *height != last_height)
+    {
+        return Err(eyre!(
+            "Can't snapshot height {height}, only the last exported height {last_height} can be"
+        ));
+    }
+
+    let height = last_height;
+
+    let block_hash = rpc.get_block_hash(height.to_usize() as u64)?.to_string();
+
+    log(&format!(
+        "Creating snapshot of height {height} ({date}) at {path:?}..."
+    ));
+
+    let encoder = zstd::Encoder::new(BufWriter::new(File::create(path)?), ZSTD_LEVEL)?;
+
+    let mut builder = tar::Builder::new(encoder);
+
+    let mut checksums = BTreeMap::new();
+
+    targets()
+        .into_iter()
+        .filter(|(_, local_path)| local_path.exists())
+        .try_for_each(|(archive_path, local_path)| -> color_eyre::Result<()> {
+            let mut files = vec![];
+
+            list_files(&local_path, &mut files)?;
+
+            files.into_iter().try_for_each(|file_path| {
+                let relative_path = file_path.strip_prefix(&local_path)?;
+
+                // Empty when the target is a file
+                let archive_path = if relative_path.as_os_str().is_empty() {
+                    archive_path.clone()
+                } else {
+                    archive_path.join(relative_path)
+                };
+
+                let file = File::open(&file_path)?;
+
+                let mut header = tar::Header::new_gnu();
+                header.set_metadata(&file.metadata()?);
+
+                let mut reader = HashingReader::new(BufReader::new(file));
+
+                builder.append_data(&mut header, &archive_path, &mut reader)?;
+
+                checksums.insert(archive_path, reader.checksum());
+
+                Ok(())
+            })
+        })?;
+
+    log(&format!("Archived {} files", checksums.len()));
+
+    let manifest = serde_json::to_vec_pretty(&Manifest {
+        version: SNAPSHOT_VERSION,
+        parser_version: env!("CARGO_PKG_VERSION").to_owned(),
+        height,
+        date,
+        block_hash,
+        checksums,
+    })?;
+
+    let mut header = tar::Header::new_gnu();
+    header.set_size(manifest.len() as u64);
+    header.set_mode(0o644);
+
+    builder.append_data(&mut header, MANIFEST_FILE_NAME, manifest.as_slice())?;
+
+    let mut writer = builder.into_inner()?.finish()?;
+
+    writer.flush()?;
+
+    writer.into_inner()?.sync_all()?;
+
+    log("Snapshot created");
+
+    Ok(())
+}
+
+///
+/// Extracts a snapshot made by `create_snapshot` in a staging folder, checks it and moves it in place.
+///
+/// Fails if the local node doesn't have the same block at the height of the snapshot
+/// or if anything that would be overwritten already exists.
+/// Taking the lock ensures that no parser is using the datasets while they're replaced.
+///
+pub fn restore_snapshot(
+    rpc: &biter::bitcoincore_rpc::Client,
+    path: &Path,
+    _lock: &OutputsLock,
+) -> color_eyre::Result<()> {
+    let targets = targets();
+
+    if let Some((_, local_path)) = targets.iter().find(|(_, local_path)| local_path.exists()) {
+        return Err(eyre!(
+            "{local_path:?} already exists, remove it before restoring a snapshot"
+        ));
+    }
+
+    log(&format!("Restoring snapshot {path:?}..."));
+
+    let staging = Path::new(STAGING_FOLDER_PATH);
+
+    if staging.exists() {
+        fs::remove_dir_all(staging)?;
+    }
+
+    let mut archive = tar::Archive::new(zstd::Decoder::new(File::open(path)?)?);
+
+    let mut checksums = BTreeMap::new();
+    let mut manifest: Option<Manifest> = None;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+
+        let archive_path = entry.path()?.into_owned();
+
+        if archive_path == Path::new(MANIFEST_FILE_NAME) {
+            let mut bytes = vec![];
+            entry.read_to_end(&mut bytes)?;
+            manifest.replace(serde_json::from_slice(&bytes)?);
+            continue;
+        }
+
+        if archive_path
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_)))
+        {
+            return Err(eyre!("Unexpected path in snapshot: {archive_path:?}"));
+        }
+
+        let file_path = staging.join(&archive_path);
+
+        fs::create_dir_all(file_path.parent().unwrap())?;
+
+        let mut reader = HashingReader::new(entry);
+        let mut writer = BufWriter::new(File::create(&file_path)?);
+
+        io::copy(&mut reader, &mut writer)?;
+
+        writer.flush()?;
+
+        checksums.insert(archive_path, reader.checksum());
+    }
+
+    let manifest = manifest.ok_or(eyre!("Missing {MANIFEST_FILE_NAME} in snapshot"))?;
+
+    if manifest.version != SNAPSHOT_VERSION {
+        return Err(eyre!(
+            "Unsupported snapshot version {}, expected {SNAPSHOT_VERSION}",
+            manifest.version
+        ));
+    }
+
+    if checksums != manifest.checksums {
+        return Err(eyre!("Checksums don't match, the snapshot is corrupted"));
+    }
+
+    let height = manifest.height;
+
+    let local_block_hash = rpc
+        .get_block_hash(height.to_usize() as u64)
+        .map_err(|_| eyre!("The node needs to be synced up to height {height} at least"))?
+        .to_string();
+
+    if local_block_hash != manifest.block_hash {
+        return Err(eyre!(
+            "Block {height} of the snapshot is {} but {local_block_hash} for the node",
+            manifest.block_hash
+        ));
+    }
+
+    fs::create_dir_all(OUTPUTS_FOLDER_PATH)?;
+
+    targets
+        .into_iter()
+        .map(|(archive_path, local_path)| (staging.join(archive_path), local_path))
+        .filter(|(staged_path, _)| staged_path.exists())
+        .try_for_each(|(staged_path, local_path)| move_path(&staged_path, &local_path))?;
+
+    fs::remove_dir_all(staging)?;
+
+    log(&format!(
+        "Restored snapshot of height {height} ({}, parser v{}), parsing will resume from there",
+        manifest.date, manifest.parser_version
+    ));
+
+    Ok(())
+}
+
+/// Renames when possible, copies and then removes when the destination is on another filesystem
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Err(error) if error.kind() == io::ErrorKind::CrossesDevices => {
+            copy_path(from, to)?;
+
+            if from.is_dir() {
+                fs::remove_dir_all(from)
+            } else {
+                fs::remove_file(from)
+            }
+        }
+        result => result,
+    }
+}
+
+fn copy_path(from: &Path, to: &Path) -> io::Result<()> {
+    if from.is_dir() {
+        fs::create_dir_all(to)?;
+
+        fs::read_dir(from)?.try_for_each(|entry| {
+            let entry = entry?;
+            copy_path(&entry.path(), &to.join(entry.file_name()))
+        })
+    } else {
+        fs::copy(from, to)?;
+
+        // Synced before the staged file is removed
+        File::open(to)?.sync_all()
+    }
+}
+
+fn list_files(path: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    if path.is_dir() {
+        fs::read_dir(path)?.try_for_each(|entry| list_files(&entry?.path(), files))
+    } else {
+        files.push(path.to_owned());
+        Ok(())
+    }
+}
+
+/// Hashes everything that is read through it
+struct HashingReader<R> {
+    inner: R,
+    engine: sha256::HashEngine,
+}
+
+impl<R> HashingReader<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            engine: sha256::Hash::engine(),
+        }
+    }
+
+    fn checksum(self) -> String {
+        sha256::Hash::from_engine(self.engine).to_string()
+    }
+}
+
+impl<R> Read for HashingReader<R>
+where
+    R: Read,
+{
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = self.inner.read(buf)?;
+
+        self.engine.input(&buf[..len]);
+
+        Ok(len)
+    }
+}
@@ -0,0 +1,28 @@
+use std::sync::atomic::Ordering;
+
+use crate::{
+    datasets::AllDatasets,
+    structs::{Config, READ_ONLY},
+    utils::log,
+};
+
+/// Returns whether the datasets are sound
+pub fn verify(config: &Config) -> color_eyre::Result<bool> {
+    READ_ONLY.store(true, Ordering::SeqCst);
+
+    log("Importing datasets...");
+
+    let datasets = AllDatasets::import(config)?;
+
+    log("Verifying datasets...");
+
+    let issues = datasets.verify();
+
+    if issues == 0 {
+        log("No issue found");
+    } else {
+        log(&format!("{issues} issues found"));
+    }
+
+    Ok(issues == 0)
+}
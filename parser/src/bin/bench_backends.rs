@@ -0,0 +1,18 @@
+use std::path::Path;
+
+use parser::{reset_logs, Databases, RECORDING_FOLDER_PATH};
+
+/// Replays the databases changes recorded with `--record-databases true` with every backend
+///
+/// Usage: `cargo run -r --bin bench_backends -- [RECORDING_DIR]`
+fn main() -> color_eyre::Result<()> {
+    color_eyre::install()?;
+
+    reset_logs();
+
+    let recording = std::env::args()
+        .nth(1)
+        .unwrap_or(RECORDING_FOLDER_PATH.to_owned());
+
+    Databases::bench_backends(Path::new(&recording))
+}
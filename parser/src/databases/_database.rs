@@ -1,99 +1,163 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     fmt::Debug,
-    fs, mem,
-    path::PathBuf,
+    fs, io,
+    path::{Path, PathBuf},
 };
 
-use allocative::Allocative;
-
-// https://docs.rs/sanakirja/latest/sanakirja/index.html
-// https://pijul.org/posts/2021-02-06-rethinking-sanakirja/
-//
-// Seems indeed much faster than ReDB and LMDB (heed)
-// But a lot has changed code wise between them so a retest wouldn't hurt
-//
-// Possible compression: https://pijul.org/posts/sanakirja-zstd/
-use sanakirja::{
-    btree::{self, page, Db_, Iter},
-    Commit, Env, Error, MutTxn, RootDb, Storable,
-};
+use allocative::{Allocative, Visitor};
+use bincode::{Decode, Encode};
+
+use crate::io::{Binary, BIN_EXTENSION, OUTPUTS_FOLDER_PATH};
+
+use super::{DefaultBackend, KvBackend, OpenGuard};
 
-#[derive(Allocative)]
-#[allocative(bound = "Key: Allocative, Value: Allocative")]
 /// There is no `cached_gets` since it's much cheaper and faster to do a parallel search first using `unsafe_get` than caching gets along the way.
-pub struct Database<Key, Value>
+pub struct Database<Key, Value, Backend = DefaultBackend<Key, Value>>
 where
-    Key: Ord + Clone + Debug + Storable,
-    Value: Storable + PartialEq,
+    Key: Ord + Clone + Debug,
+    Value: PartialEq,
+    Backend: KvBackend<Key, Value>,
 {
     pub cached_puts: BTreeMap<Key, Value>,
     pub cached_dels: BTreeSet<Key>,
     path: PathBuf,
-    #[allocative(skip)]
-    db: Db_<Key, Value, page::Page<Key, Value>>,
-    #[allocative(skip)]
-    txn: MutTxn<Env, ()>,
+    backend: Backend,
+    _guard: Option<OpenGuard>,
 }
 
-const ROOT_DB: usize = 0;
-const PAGE_SIZE: u64 = 4096;
+// Can't be derived because of the default backend
+impl<Key, Value, Backend> Allocative for Database<Key, Value, Backend>
+where
+    Key: Ord + Clone + Debug + Allocative,
+    Value: PartialEq + Allocative,
+    Backend: KvBackend<Key, Value>,
+{
+    fn visit<'a, 'b: 'a>(&self, visitor: &'a mut Visitor<'b>) {
+        let mut visitor = visitor.enter_self_sized::<Self>();
+        visitor.visit_field(allocative::Key::new("cached_puts"), &self.cached_puts);
+        visitor.visit_field(allocative::Key::new("cached_dels"), &self.cached_dels);
+        visitor.visit_field(allocative::Key::new("path"), &self.path);
+        visitor.exit();
+    }
+}
+
+pub const WRITE_AHEAD_LOGS_FOLDER: &str = "write_ahead_logs";
+const COMPACTION_FOLDER: &str = "compaction";
+/// Share of the file not used by entries above which a background compaction is worth its writes
+const MIN_WASTED_RATIO: f64 = 0.5;
 
-impl<Key, Value> Database<Key, Value>
+impl<Key, Value, Backend> Database<Key, Value, Backend>
 where
-    Key: Ord + Clone + Debug + Storable,
-    Value: Storable + PartialEq,
+    Key: Ord + Clone + Debug + Encode + Decode,
+    Value: PartialEq + Encode + Decode,
+    Backend: KvBackend<Key, Value>,
 {
+    /// Waits if the database is being compacted and replays the write-ahead log first if the last commit was interrupted
     pub fn open(path: PathBuf) -> color_eyre::Result<Self> {
-        let env = unsafe { Env::new_nolock(&path, PAGE_SIZE, 1)? };
+        let guard = OpenGuard::acquire(&path);
+
+        Self::replay_write_ahead_log(&path)?;
+
+        let mut db = Self::_open(path)?;
+        db._guard = Some(guard);
+        Ok(db)
+    }
+
+    fn replay_write_ahead_log(path: &Path) -> color_eyre::Result<()> {
+        let write_ahead_log_path = Self::write_ahead_log_path(path);
+
+        if write_ahead_log_path.exists() {
+            let (cached_dels, cached_puts) = Binary::import(&write_ahead_log_path)?;
+
+            let mut db = Self::_open(path.to_owned())?;
+            db.cached_dels = cached_dels;
+            db.cached_puts = cached_puts;
+            db.commit()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes a compacted copy which then replaces the old file, needs the database to be closed
+    pub fn compact(path: &Path) -> color_eyre::Result<()> {
+        Self::replay_write_ahead_log(path)?;
+
+        let compacted_path = PathBuf::from(format!(
+            "{OUTPUTS_FOLDER_PATH}/{COMPACTION_FOLDER}/{}",
+            flat_name(path)
+        ));
+
+        fs::create_dir_all(compacted_path.parent().unwrap())?;
+
+        // Leftover from an interrupted compaction
+        let _ = fs::remove_file(&compacted_path);
 
-        let mut txn = Env::mut_txn_begin(env)?;
+        let source = Backend::open(path)?;
 
-        let db = txn
-            .root_db(ROOT_DB)
-            .unwrap_or_else(|| unsafe { btree::create_db_(&mut txn).unwrap() });
+        source.compact_to(&compacted_path)?;
+
+        drop(source);
+
+        fs::rename(compacted_path, path)?;
+
+        Ok(())
+    }
+
+    /// Only compacts if enough of the file is wasted, returns whether it did
+    pub fn compact_if_fragmented(path: &Path) -> color_eyre::Result<bool> {
+        let file_bytes = fs::metadata(path)?.len();
+
+        if file_bytes == 0 {
+            return Ok(false);
+        }
+
+        let live_bytes = Backend::open(path)?.live_bytes();
+
+        let wasted_ratio = 1.0 - live_bytes as f64 / file_bytes as f64;
+
+        if wasted_ratio < MIN_WASTED_RATIO {
+            return Ok(false);
+        }
+
+        Self::compact(path)?;
+
+        Ok(true)
+    }
+
+    fn _open(path: PathBuf) -> color_eyre::Result<Self> {
+        let backend = Backend::open(&path)?;
 
         Ok(Self {
             path,
             cached_puts: BTreeMap::default(),
             cached_dels: BTreeSet::default(),
-            db,
-            txn,
+            backend,
+            _guard: None,
         })
     }
 
-    pub fn iter(&self) -> Iter<'_, MutTxn<Env, ()>, Key, Value, page::Page<Key, Value>> {
-        btree::iter(&self.txn, &self.db, None).unwrap()
+    pub fn iter(&self) -> impl Iterator<Item = (Key, Value)> + '_ {
+        self.backend.iter()
     }
 
-    pub fn iter_collect(&self) -> BTreeMap<Key, Value>
+    pub fn iter_collect(&self) -> BTreeMap<Key, Value> {
+        self.iter().collect::<_>()
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Value>
     where
         Value: Clone,
     {
-        self.iter()
-            .map(|r| r.unwrap())
-            .map(|(key, value)| (key.clone(), value.clone()))
-            .collect::<_>()
-    }
-
-    pub fn get(&self, key: &Key) -> Option<&Value> {
         if let Some(cached_put) = self.get_from_puts(key) {
-            return Some(cached_put);
+            return Some(cached_put.clone());
         }
 
         self.db_get(key)
     }
 
-    pub fn db_get(&self, key: &Key) -> Option<&Value> {
-        let option = btree::get(&self.txn, &self.db, key, None).unwrap();
-
-        if let Some((key_found, v)) = option {
-            if key == key_found {
-                return Some(v);
-            }
-        }
-
-        None
+    pub fn db_get(&self, key: &Key) -> Option<Value> {
+        self.backend.get(key)
     }
 
     #[inline(always)]
@@ -148,54 +212,105 @@ where
         self.cached_puts.insert(key, value)
     }
 
-    fn db_multi_put(&mut self, tree: BTreeMap<Key, Value>) -> Result<(), Error> {
-        tree.into_iter()
-            .try_for_each(|(key, value)| -> Result<(), Error> {
-                btree::put(&mut self.txn, &mut self.db, &key, &value)?;
-                Ok(())
-            })
+    /// Idempotent since deletions are applied before insertions
+    fn commit(self) -> color_eyre::Result<()> {
+        if self.cached_dels.is_empty() && self.cached_puts.is_empty() {
+            return Ok(());
+        }
+
+        let write_ahead_log_path = Self::write_ahead_log_path(&self.path);
+
+        self.backend.commit(self.cached_dels, self.cached_puts)?;
+
+        let _ = fs::remove_file(write_ahead_log_path);
+
+        Ok(())
     }
 
-    fn db_multi_del(&mut self, tree: BTreeSet<Key>) -> Result<(), Error> {
-        tree.into_iter().try_for_each(|key| -> Result<(), Error> {
-            btree::del(&mut self.txn, &mut self.db, &key, None)?;
-            Ok(())
-        })
+    fn export_changes(&self, path: &Path) -> color_eyre::Result<()>
+    where
+        Value: Debug,
+    {
+        if self.cached_dels.is_empty() && self.cached_puts.is_empty() {
+            return Ok(());
+        }
+
+        fs::create_dir_all(path.parent().unwrap())?;
+
+        Binary::export(path, &(&self.cached_dels, &self.cached_puts))
+    }
+
+    fn write_ahead_log_path(path: &Path) -> PathBuf {
+        PathBuf::from(format!(
+            "{OUTPUTS_FOLDER_PATH}/{WRITE_AHEAD_LOGS_FOLDER}/{}.{BIN_EXTENSION}",
+            flat_name(path)
+        ))
     }
 }
 
+/// Flattened since the databases folders are scanned by name when opening them
+pub fn flat_name(path: &Path) -> String {
+    path.strip_prefix(OUTPUTS_FOLDER_PATH)
+        .unwrap_or(path)
+        .to_str()
+        .unwrap()
+        .replace('/', "-")
+}
+
+/// Removes the write-ahead logs of every database under `root` so that they can't be replayed on fresh ones
+pub fn remove_write_ahead_logs(root: &Path) -> io::Result<()> {
+    let prefix = format!("{}-", flat_name(root));
+
+    let Ok(entries) = fs::read_dir(format!("{OUTPUTS_FOLDER_PATH}/{WRITE_AHEAD_LOGS_FOLDER}"))
+    else {
+        return Ok(());
+    };
+
+    entries
+        .map(|entry| entry.map(|entry| entry.path()))
+        .filter(|path| {
+            path.as_ref().map_or(true, |path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(&prefix))
+            })
+        })
+        .try_for_each(|path| fs::remove_file(path?))
+}
+
 pub trait AnyDatabase {
-    fn export(self) -> color_eyre::Result<(), Error>;
-    fn boxed_export(self: Box<Self>) -> color_eyre::Result<(), Error>;
+    /// Saves the pending changes so that they can be replayed if the commit that follows is interrupted
+    fn write_ahead_log(&self) -> color_eyre::Result<()>;
+    /// Saves the pending changes in `folder` to be replayed by `bench_backends`
+    fn record(&self, folder: &Path) -> color_eyre::Result<()>;
+    fn export(self) -> color_eyre::Result<()>;
+    fn boxed_export(self: Box<Self>) -> color_eyre::Result<()>;
     #[allow(unused)]
     fn defragment(self);
     fn boxed_defragment(self: Box<Self>);
     fn destroy(self);
 }
 
-impl<Key, Value> AnyDatabase for Database<Key, Value>
+impl<Key, Value, Backend> AnyDatabase for Database<Key, Value, Backend>
 where
-    Key: Ord + Clone + Debug + Storable,
-    Value: Storable + PartialEq + Clone,
+    Key: Ord + Clone + Debug + Encode + Decode,
+    Value: PartialEq + Clone + Debug + Encode + Decode,
+    Backend: KvBackend<Key, Value>,
 {
-    fn export(self) -> color_eyre::Result<(), Error> {
-        Box::new(self).boxed_export()
+    fn write_ahead_log(&self) -> color_eyre::Result<()> {
+        self.export_changes(&Self::write_ahead_log_path(&self.path))
     }
 
-    fn boxed_export(mut self: Box<Self>) -> color_eyre::Result<(), Error> {
-        if self.cached_dels.is_empty() && self.cached_puts.is_empty() {
-            return Ok(());
-        }
-
-        let cached_dels = mem::take(&mut self.cached_dels);
-        self.db_multi_del(cached_dels)?;
-
-        let cached_puts = mem::take(&mut self.cached_puts);
-        self.db_multi_put(cached_puts)?;
+    fn record(&self, folder: &Path) -> color_eyre::Result<()> {
+        self.export_changes(&folder.join(format!("{}.{BIN_EXTENSION}", flat_name(&self.path))))
+    }
 
-        self.txn.set_root(ROOT_DB, self.db.db.into());
+    fn export(self) -> color_eyre::Result<()> {
+        Box::new(self).boxed_export()
+    }
 
-        self.txn.commit()
+    fn boxed_export(self: Box<Self>) -> color_eyre::Result<()> {
+        (*self).commit()
     }
 
     fn defragment(self) {
@@ -203,20 +318,11 @@ where
     }
 
     fn boxed_defragment(self: Box<Self>) {
-        let btree = self.iter_collect();
-
         let path = self.path.to_owned();
 
-        self.destroy();
-
-        let mut db = Self::open(path).unwrap();
-
-        if !db.is_empty() {
-            panic!()
-        }
+        drop(self);
 
-        db.cached_puts = btree;
-        db.export().unwrap();
+        Self::compact(&path).unwrap();
     }
 
     fn destroy(self) {
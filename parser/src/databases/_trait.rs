@@ -1,4 +1,7 @@
-use std::{fs, io, path::PathBuf};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
 
 use crate::{
     io::OUTPUTS_FOLDER_PATH,
@@ -6,7 +9,7 @@ use crate::{
     utils::log,
 };
 
-use super::AnyDatabase;
+use super::{remove_write_ahead_logs, AnyDatabase, BenchResults, CompactionCandidate};
 
 pub trait AnyDatabaseGroup
 where
@@ -38,6 +41,7 @@ where
 
         self.reset_metadata();
         self.remove_dir_all()?;
+        remove_write_ahead_logs(&Self::root())?;
         self.create_dir_all()?;
 
         Ok(())
@@ -45,6 +49,45 @@ where
 
     fn reset_metadata(&mut self);
 
+    /// Needs to know the type of the database from its path, returns whether it was fragmented enough to be compacted
+    fn compact_db(path: &Path) -> color_eyre::Result<bool>;
+
+    /// Same as `compact_db`
+    fn bench_db(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults>;
+
+    fn compaction_candidates() -> Vec<CompactionCandidate> {
+        let Ok(folder) = fs::read_dir(Self::root()) else {
+            return vec![];
+        };
+
+        folder
+            .flatten()
+            .map(|entry| entry.path())
+            .flat_map(|path| {
+                if path.is_dir() {
+                    fs::read_dir(path)
+                        .map(|folder| folder.flatten().map(|entry| entry.path()).collect())
+                        .unwrap_or_default()
+                } else {
+                    vec![path]
+                }
+            })
+            .filter(|path| {
+                path.is_file()
+                    && !path
+                        .file_name()
+                        .unwrap()
+                        .to_str()
+                        .unwrap()
+                        .starts_with("metadata")
+            })
+            .map(|path| CompactionCandidate {
+                path,
+                compact: Self::compact_db,
+            })
+            .collect()
+    }
+
     fn root() -> PathBuf {
         let folder = Self::folder();
         PathBuf::from(format!("{OUTPUTS_FOLDER_PATH}/databases/{folder}"))
@@ -2,6 +2,7 @@ use std::{
     collections::BTreeMap,
     fs, mem,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
 };
 
 use allocative::Allocative;
@@ -14,7 +15,7 @@ use crate::{
     utils::time,
 };
 
-use super::{AnyDatabase, AnyDatabaseGroup, Database as _Database, Metadata};
+use super::{bench, AnyDatabase, AnyDatabaseGroup, BenchResults, Database as _Database, Metadata};
 
 type Key = u32;
 type Value = AddressData;
@@ -64,7 +65,7 @@ impl AddressIndexToAddressData {
         self.get(&db_index).unwrap().get_from_puts(key)
     }
 
-    pub fn unsafe_get_from_db(&self, key: &Key) -> Option<&Value> {
+    pub fn unsafe_get_from_db(&self, key: &Key) -> Option<Value> {
         let db_index = Self::db_index(key);
 
         self.get(&db_index).unwrap().db_get(key)
@@ -98,8 +99,7 @@ impl AddressIndexToAddressData {
 
                     database
                         .iter()
-                        .map(|r| r.unwrap().1)
-                        .for_each(|address_data| s.increment(address_data).unwrap());
+                        .for_each(|(_, address_data)| s.increment(&address_data).unwrap());
 
                     s
                 })
@@ -129,6 +129,14 @@ impl AnyDatabaseGroup for AddressIndexToAddressData {
         self.metadata.reset();
     }
 
+    fn compact_db(path: &Path) -> color_eyre::Result<bool> {
+        Database::compact_if_fragmented(path)
+    }
+
+    fn bench_db(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults> {
+        bench::<Key, Value>(path, changes)
+    }
+
     fn folder<'a>() -> &'a str {
         "address_index_to_address_data"
     }
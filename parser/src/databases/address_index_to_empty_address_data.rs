@@ -2,6 +2,7 @@ use std::{
     collections::BTreeMap,
     fs, mem,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
 };
 
 use allocative::Allocative;
@@ -10,7 +11,8 @@ use itertools::Itertools;
 use crate::structs::{Date, EmptyAddressData, Height};
 
 use super::{
-    AnyDatabase, AnyDatabaseGroup, Database as _Database, Metadata, ADDRESS_INDEX_DB_MAX_SIZE,
+    bench, AnyDatabase, AnyDatabaseGroup, BenchResults, Database as _Database, Metadata,
+    ADDRESS_INDEX_DB_MAX_SIZE,
 };
 
 type Key = u32;
@@ -59,7 +61,7 @@ impl AddressIndexToEmptyAddressData {
         self.get(&db_index).and_then(|db| db.get_from_puts(key))
     }
 
-    pub fn unsafe_get_from_db(&self, key: &Key) -> Option<&Value> {
+    pub fn unsafe_get_from_db(&self, key: &Key) -> Option<Value> {
         let db_index = Self::db_index(key);
 
         self.get(&db_index)
@@ -108,6 +110,14 @@ impl AnyDatabaseGroup for AddressIndexToEmptyAddressData {
         self.metadata.reset();
     }
 
+    fn compact_db(path: &Path) -> color_eyre::Result<bool> {
+        Database::compact_if_fragmented(path)
+    }
+
+    fn bench_db(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults> {
+        bench::<Key, Value>(path, changes)
+    }
+
     fn folder<'a>() -> &'a str {
         "address_index_to_empty_address_data"
     }
@@ -9,7 +9,7 @@ use itertools::Itertools;
 
 use crate::structs::{Address, Date, Height, U8x19, U8x31};
 
-use super::{AnyDatabase, AnyDatabaseGroup, Database, Metadata};
+use super::{bench, AnyDatabase, AnyDatabaseGroup, BenchResults, Database, Metadata};
 
 type Value = u32;
 type U8x19Database = Database<U8x19, Value>;
@@ -86,7 +86,7 @@ impl AddressToAddressIndex {
 
     /// Doesn't check if the database is open contrary to `safe_get` which does and opens if needed.
     /// Though it makes it easy to use with rayon
-    pub fn unsafe_get(&self, address: &Address) -> Option<&Value> {
+    pub fn unsafe_get(&self, address: &Address) -> Option<Value> {
         match address {
             Address::Empty(key) => self.empty.as_ref().unwrap().get(key),
             Address::Unknown(key) => self.unknown.as_ref().unwrap().get(key),
@@ -367,6 +367,28 @@ impl AnyDatabaseGroup for AddressToAddressIndex {
         self.metadata.reset()
     }
 
+    fn compact_db(path: &Path) -> color_eyre::Result<bool> {
+        let kind = path.parent().and_then(|parent| parent.file_name());
+
+        match kind.and_then(|kind| kind.to_str()) {
+            Some("p2pk" | "p2pkh" | "p2sh" | "p2wpkh") => {
+                U8x19Database::compact_if_fragmented(path)
+            }
+            Some("p2wsh" | "p2tr") => U8x31Database::compact_if_fragmented(path),
+            _ => U32Database::compact_if_fragmented(path),
+        }
+    }
+
+    fn bench_db(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults> {
+        let kind = path.parent().and_then(|parent| parent.file_name());
+
+        match kind.and_then(|kind| kind.to_str()) {
+            Some("p2pk" | "p2pkh" | "p2sh" | "p2wpkh") => bench::<U8x19, Value>(path, changes),
+            Some("p2wsh" | "p2tr") => bench::<U8x31, Value>(path, changes),
+            _ => bench::<u32, Value>(path, changes),
+        }
+    }
+
     fn folder<'a>() -> &'a str {
         "address_to_address_index"
     }
@@ -0,0 +1,112 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use bincode::{config, decode_from_std_read, encode_into_std_write, Decode, Encode};
+
+use super::KvBackend;
+
+///
+/// Log structured store, everything is kept in memory in a `BTreeMap`.
+///
+/// Each commit appends its deletions and insertions to the file which is replayed when opening,
+/// compacting collapses the log into a single record.
+///
+/// Much more RAM hungry than sanakirja but writes are sequential and reads never touch the disk.
+///
+/// Meant for tests and benchmarks only, the tx databases of mainnet don't fit in memory.
+///
+pub struct LogBackend<Key, Value> {
+    path: PathBuf,
+    map: BTreeMap<Key, Value>,
+}
+
+type Record<Key, Value> = (BTreeSet<Key>, BTreeMap<Key, Value>);
+
+impl<Key, Value> LogBackend<Key, Value>
+where
+    Key: Ord + Encode,
+    Value: Encode,
+{
+    fn append(
+        path: &Path,
+        dels: &BTreeSet<Key>,
+        puts: &BTreeMap<Key, Value>,
+    ) -> color_eyre::Result<()> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        let mut writer = BufWriter::new(file);
+
+        encode_into_std_write((dels, puts), &mut writer, config::standard())?;
+
+        writer.flush()?;
+
+        writer.get_ref().sync_all()?;
+
+        Ok(())
+    }
+}
+
+impl<Key, Value> KvBackend<Key, Value> for LogBackend<Key, Value>
+where
+    Key: Ord + Clone + Encode + Decode,
+    Value: Clone + Encode + Decode,
+{
+    const NAME: &'static str = "log";
+
+    fn open(path: &Path) -> color_eyre::Result<Self> {
+        let mut map = BTreeMap::default();
+
+        if let Ok(file) = File::open(path) {
+            let mut reader = BufReader::new(file);
+
+            while !reader.fill_buf()?.is_empty() {
+                // A record cut short by a crash is dropped, the database's write-ahead log has it
+                let Ok((dels, puts)) = decode_from_std_read::<Record<Key, Value>, _, _>(
+                    &mut reader,
+                    config::standard(),
+                ) else {
+                    break;
+                };
+
+                dels.iter().for_each(|key| {
+                    map.remove(key);
+                });
+
+                map.extend(puts);
+            }
+        }
+
+        Ok(Self {
+            path: path.to_owned(),
+            map,
+        })
+    }
+
+    fn get(&self, key: &Key) -> Option<Value> {
+        self.map.get(key).cloned()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Key, Value)> + '_> {
+        Box::new(
+            self.map
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        )
+    }
+
+    fn commit(self, dels: BTreeSet<Key>, puts: BTreeMap<Key, Value>) -> color_eyre::Result<()> {
+        if dels.is_empty() && puts.is_empty() {
+            return Ok(());
+        }
+
+        Self::append(&self.path, &dels, &puts)
+    }
+
+    fn compact_to(&self, path: &Path) -> color_eyre::Result<()> {
+        Self::append(path, &BTreeSet::default(), &self.map)
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+mod log_backend;
+mod redb_backend;
+mod sanakirja_backend;
+
+pub use log_backend::*;
+pub use redb_backend::*;
+pub use sanakirja_backend::*;
+
+/// Switching requires a fresh `./out/databases` folder since the file formats are different
+#[cfg(not(any(feature = "log-backend", feature = "redb-backend")))]
+pub type DefaultBackend<Key, Value> = SanakirjaBackend<Key, Value>;
+#[cfg(feature = "log-backend")]
+pub type DefaultBackend<Key, Value> = LogBackend<Key, Value>;
+#[cfg(all(feature = "redb-backend", not(feature = "log-backend")))]
+pub type DefaultBackend<Key, Value> = RedbBackend<Key, Value>;
+
+/// Key-value store in which a `Database` flushes its cached puts and dels
+///
+/// Values are returned owned so that stores which decode or copy them on read can implement it
+pub trait KvBackend<Key, Value>
+where
+    Self: Sized,
+{
+    const NAME: &'static str;
+
+    /// Creates the store if it doesn't exist
+    fn open(path: &Path) -> color_eyre::Result<Self>;
+
+    fn get(&self, key: &Key) -> Option<Value>;
+
+    /// Sorted by key
+    fn iter(&self) -> Box<dyn Iterator<Item = (Key, Value)> + '_>;
+
+    /// Applies the deletions before the insertions and persists them
+    fn commit(self, dels: BTreeSet<Key>, puts: BTreeMap<Key, Value>) -> color_eyre::Result<()>;
+
+    /// Writes a copy of the store without its free space to `path`
+    fn compact_to(&self, path: &Path) -> color_eyre::Result<()>;
+
+    /// Approximate size of the entries without any overhead
+    fn live_bytes(&self) -> u64 {
+        (self.iter().count() * (size_of::<Key>() + size_of::<Value>())) as u64
+    }
+}
@@ -0,0 +1,142 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    marker::PhantomData,
+    path::Path,
+};
+
+use bincode::{
+    config::{self, BigEndian, Configuration, Fixint},
+    decode_from_slice, encode_to_vec, Decode, Encode,
+};
+use redb::{Database, ReadOnlyTable, ReadableTable, TableDefinition, TableError};
+
+use super::KvBackend;
+
+const TABLE: TableDefinition<&[u8], &[u8]> = TableDefinition::new("entries");
+
+/// Big endian with fixed size integers so that the bytes of the (unsigned) keys sort like the keys
+const CONFIG: Configuration<BigEndian, Fixint> = config::standard()
+    .with_big_endian()
+    .with_fixed_int_encoding();
+
+///
+/// Copy-on-write B-tree on disk, entries are stored encoded with bincode.
+///
+/// Unlike the log backend nothing is kept in memory besides redb's page cache,
+/// reads decode the value instead of returning a view in a memory map like sanakirja.
+///
+pub struct RedbBackend<Key, Value> {
+    db: Database,
+    /// `None` until the first commit
+    table: Option<ReadOnlyTable<&'static [u8], &'static [u8]>>,
+    _phantom: PhantomData<(Key, Value)>,
+}
+
+impl<Key, Value> RedbBackend<Key, Value>
+where
+    Key: Encode + Decode,
+    Value: Encode + Decode,
+{
+    fn encode<T: Encode>(value: &T) -> Vec<u8> {
+        encode_to_vec(value, CONFIG).unwrap()
+    }
+
+    fn decode<T: Decode>(bytes: &[u8]) -> T {
+        decode_from_slice(bytes, CONFIG).unwrap().0
+    }
+}
+
+impl<Key, Value> KvBackend<Key, Value> for RedbBackend<Key, Value>
+where
+    Key: Ord + Clone + Encode + Decode,
+    Value: Clone + Encode + Decode,
+{
+    const NAME: &'static str = "redb";
+
+    fn open(path: &Path) -> color_eyre::Result<Self> {
+        let db = Database::create(path)?;
+
+        let table = match db.begin_read()?.open_table(TABLE) {
+            Ok(table) => Some(table),
+            Err(TableError::TableDoesNotExist(_)) => None,
+            Err(error) => return Err(error.into()),
+        };
+
+        Ok(Self {
+            db,
+            table,
+            _phantom: PhantomData,
+        })
+    }
+
+    fn get(&self, key: &Key) -> Option<Value> {
+        self.table
+            .as_ref()?
+            .get(Self::encode(key).as_slice())
+            .unwrap()
+            .map(|value| Self::decode(value.value()))
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Key, Value)> + '_> {
+        let Some(table) = self.table.as_ref() else {
+            return Box::new(std::iter::empty());
+        };
+
+        Box::new(table.iter().unwrap().map(|entry| {
+            let (key, value) = entry.unwrap();
+            (Self::decode(key.value()), Self::decode(value.value()))
+        }))
+    }
+
+    fn commit(self, dels: BTreeSet<Key>, puts: BTreeMap<Key, Value>) -> color_eyre::Result<()> {
+        let Self { db, table, .. } = self;
+
+        // Otherwise the pages it reads can't be reused by the write
+        drop(table);
+
+        let txn = db.begin_write()?;
+
+        {
+            let mut table = txn.open_table(TABLE)?;
+
+            dels.iter().try_for_each(|key| -> color_eyre::Result<()> {
+                table.remove(Self::encode(key).as_slice())?;
+                Ok(())
+            })?;
+
+            puts.iter()
+                .try_for_each(|(key, value)| -> color_eyre::Result<()> {
+                    table.insert(Self::encode(key).as_slice(), Self::encode(value).as_slice())?;
+                    Ok(())
+                })?;
+        }
+
+        txn.commit()?;
+
+        Ok(())
+    }
+
+    fn compact_to(&self, path: &Path) -> color_eyre::Result<()> {
+        let compacted = Database::create(path)?;
+
+        let txn = compacted.begin_write()?;
+
+        {
+            let mut compacted_table = txn.open_table(TABLE)?;
+
+            if let Some(table) = self.table.as_ref() {
+                table
+                    .iter()?
+                    .try_for_each(|entry| -> color_eyre::Result<()> {
+                        let (key, value) = entry?;
+                        compacted_table.insert(key.value(), value.value())?;
+                        Ok(())
+                    })?;
+            }
+        }
+
+        txn.commit()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+// https://docs.rs/sanakirja/latest/sanakirja/index.html
+// https://pijul.org/posts/2021-02-06-rethinking-sanakirja/
+//
+// Seems indeed much faster than ReDB and LMDB (heed)
+// But a lot has changed code wise between them so a retest wouldn't hurt
+//
+// Possible compression: https://pijul.org/posts/sanakirja-zstd/
+use sanakirja::{
+    btree::{self, page, Db_},
+    Commit, Env, Error, MutTxn, RootDb, Storable,
+};
+
+use super::KvBackend;
+
+const ROOT_DB: usize = 0;
+const PAGE_SIZE: u64 = 4096;
+
+/// Memory mapped B-tree, values are read straight from the file
+pub struct SanakirjaBackend<Key, Value> {
+    db: Db_<Key, Value, page::Page<Key, Value>>,
+    txn: MutTxn<Env, ()>,
+}
+
+impl<Key, Value> SanakirjaBackend<Key, Value>
+where
+    Key: Ord + Storable,
+    Value: Storable,
+{
+    fn iter_ref(&self) -> impl Iterator<Item = (&Key, &Value)> {
+        btree::iter(&self.txn, &self.db, None)
+            .unwrap()
+            .map(|r| r.unwrap())
+    }
+
+    fn put<'a>(&mut self, entries: impl Iterator<Item = (&'a Key, &'a Value)>) -> Result<(), Error>
+    where
+        Key: 'a,
+        Value: 'a,
+    {
+        entries.into_iter().try_for_each(|(key, value)| {
+            btree::put(&mut self.txn, &mut self.db, key, value)?;
+            Ok(())
+        })
+    }
+
+    fn del(&mut self, keys: BTreeSet<Key>) -> Result<(), Error> {
+        keys.into_iter().try_for_each(|key| {
+            btree::del(&mut self.txn, &mut self.db, &key, None)?;
+            Ok(())
+        })
+    }
+
+    fn _commit(mut self) -> Result<(), Error> {
+        self.txn.set_root(ROOT_DB, self.db.db.into());
+
+        self.txn.commit()
+    }
+}
+
+impl<Key, Value> KvBackend<Key, Value> for SanakirjaBackend<Key, Value>
+where
+    Key: Ord + Clone + Storable,
+    Value: Clone + Storable,
+{
+    const NAME: &'static str = "sanakirja";
+
+    fn open(path: &Path) -> color_eyre::Result<Self> {
+        let env = unsafe { Env::new_nolock(path, PAGE_SIZE, 1)? };
+
+        let mut txn = Env::mut_txn_begin(env)?;
+
+        let db = txn
+            .root_db(ROOT_DB)
+            .unwrap_or_else(|| unsafe { btree::create_db_(&mut txn).unwrap() });
+
+        Ok(Self { db, txn })
+    }
+
+    fn get(&self, key: &Key) -> Option<Value> {
+        let option = btree::get(&self.txn, &self.db, key, None).unwrap();
+
+        if let Some((key_found, v)) = option {
+            if key == key_found {
+                return Some(v.clone());
+            }
+        }
+
+        None
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = (Key, Value)> + '_> {
+        Box::new(
+            self.iter_ref()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        )
+    }
+
+    fn commit(mut self, dels: BTreeSet<Key>, puts: BTreeMap<Key, Value>) -> color_eyre::Result<()> {
+        self.del(dels)?;
+
+        self.put(puts.iter())?;
+
+        self._commit()?;
+
+        Ok(())
+    }
+
+    fn compact_to(&self, path: &Path) -> color_eyre::Result<()> {
+        let mut compacted = Self::open(path)?;
+
+        compacted.put(self.iter_ref())?;
+
+        compacted._commit()?;
+
+        Ok(())
+    }
+}
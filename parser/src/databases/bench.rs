@@ -0,0 +1,140 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    ops::AddAssign,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use bincode::{Decode, Encode};
+
+use crate::io::{Binary, OUTPUTS_FOLDER_PATH};
+
+use super::{flat_name, KvBackend, LogBackend, RedbBackend, SanakirjaBackend};
+
+pub const RECORDING_FOLDER_PATH: &str = "./out/databases_recording";
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct BenchResult {
+    pub writes: Duration,
+    pub reads: Duration,
+    pub bytes: u64,
+}
+
+impl AddAssign for BenchResult {
+    fn add_assign(&mut self, rhs: Self) {
+        self.writes += rhs.writes;
+        self.reads += rhs.reads;
+        self.bytes += rhs.bytes;
+    }
+}
+
+/// Results by backend name
+pub type BenchResults = BTreeMap<&'static str, BenchResult>;
+
+/// Recorded changes of each database, sorted by export height
+pub fn recorded_databases(recording: &Path) -> color_eyre::Result<BTreeMap<String, Vec<PathBuf>>> {
+    let mut exports = fs::read_dir(recording)?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter_map(|path| {
+            let height = path.file_name()?.to_str()?.parse::<u32>().ok()?;
+            Some((height, path))
+        })
+        .collect::<Vec<_>>();
+
+    exports.sort_unstable_by_key(|(height, _)| *height);
+
+    let mut databases: BTreeMap<String, Vec<PathBuf>> = BTreeMap::new();
+
+    exports
+        .into_iter()
+        .try_for_each(|(_, folder)| -> color_eyre::Result<()> {
+            fs::read_dir(folder)?.flatten().for_each(|entry| {
+                let path = entry.path();
+                let name = path.file_stem().unwrap().to_str().unwrap().to_owned();
+                databases.entry(name).or_default().push(path);
+            });
+            Ok(())
+        })?;
+
+    Ok(databases)
+}
+
+/// Replays the recorded changes of a database with every backend
+pub fn bench<Key, Value>(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults>
+where
+    Key: Ord + Clone + Encode + Decode + sanakirja::Storable,
+    Value: Clone + Encode + Decode + sanakirja::Storable,
+{
+    Ok(BTreeMap::from([
+        (
+            SanakirjaBackend::<Key, Value>::NAME,
+            replay::<Key, Value, SanakirjaBackend<Key, Value>>(path, changes)?,
+        ),
+        (
+            LogBackend::<Key, Value>::NAME,
+            replay::<Key, Value, LogBackend<Key, Value>>(path, changes)?,
+        ),
+        (
+            RedbBackend::<Key, Value>::NAME,
+            replay::<Key, Value, RedbBackend<Key, Value>>(path, changes)?,
+        ),
+    ]))
+}
+
+/// Commits every recorded change in order, then reopens the database and reads back every inserted key
+fn replay<Key, Value, Backend>(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResult>
+where
+    Key: Ord + Clone + Decode,
+    Value: Decode,
+    Backend: KvBackend<Key, Value>,
+{
+    let bench_path = PathBuf::from(format!(
+        "{OUTPUTS_FOLDER_PATH}/bench/{}/{}",
+        Backend::NAME,
+        flat_name(path)
+    ));
+
+    fs::create_dir_all(bench_path.parent().unwrap())?;
+
+    let _ = fs::remove_file(&bench_path);
+
+    let mut result = BenchResult::default();
+
+    let mut keys = BTreeSet::new();
+
+    changes
+        .iter()
+        .try_for_each(|path| -> color_eyre::Result<()> {
+            let (dels, puts): (BTreeSet<Key>, BTreeMap<Key, Value>) = Binary::import(path)?;
+
+            keys.extend(puts.keys().cloned());
+
+            let instant = Instant::now();
+
+            Backend::open(&bench_path)?.commit(dels, puts)?;
+
+            result.writes += instant.elapsed();
+
+            Ok(())
+        })?;
+
+    let instant = Instant::now();
+
+    let backend = Backend::open(&bench_path)?;
+
+    keys.iter().for_each(|key| {
+        backend.get(key);
+    });
+
+    result.reads = instant.elapsed();
+
+    drop(backend);
+
+    result.bytes = fs::metadata(&bench_path).map_or(0, |metadata| metadata.len());
+
+    fs::remove_file(&bench_path)?;
+
+    Ok(result)
+}
@@ -0,0 +1,152 @@
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Condvar, Mutex},
+    thread::{self, JoinHandle},
+};
+
+use crate::{io::OUTPUTS_FOLDER_PATH, utils::log, Exit};
+
+const CURSOR_FILE_NAME: &str = "compaction_cursor";
+
+/// Databases opened by the parser and the one being compacted, they're mutually exclusive
+static REGISTRY: Mutex<Registry> = Mutex::new(Registry {
+    open: BTreeSet::new(),
+    compacting: None,
+});
+static COMPACTED: Condvar = Condvar::new();
+
+struct Registry {
+    open: BTreeSet<PathBuf>,
+    compacting: Option<PathBuf>,
+}
+
+/// Registers a database as open for as long as it lives, waits first if it's being compacted
+#[derive(Debug)]
+pub struct OpenGuard(PathBuf);
+
+impl OpenGuard {
+    pub fn acquire(path: &Path) -> Self {
+        let mut registry = REGISTRY.lock().unwrap();
+
+        while registry.compacting.as_deref() == Some(path) {
+            registry = COMPACTED.wait(registry).unwrap();
+        }
+
+        registry.open.insert(path.to_owned());
+
+        Self(path.to_owned())
+    }
+}
+
+impl Drop for OpenGuard {
+    fn drop(&mut self) {
+        REGISTRY.lock().unwrap().open.remove(&self.0);
+    }
+}
+
+pub type CompactFn = fn(&Path) -> color_eyre::Result<bool>;
+
+pub struct CompactionCandidate {
+    pub path: PathBuf,
+    pub compact: CompactFn,
+}
+
+///
+/// Online alternative to `Databases::defragment`.
+///
+/// Rewrites the fragmented databases one at a time in a background thread while the parser keeps going,
+/// skipping the ones that are open and making the parser wait if it needs the one being compacted.
+///
+/// Memory usage is bounded since entries are streamed from the old file to the new one.
+///
+#[derive(Default)]
+pub struct Compaction {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Compaction {
+    /// Does nothing if the previous pass is still running
+    pub fn start(&mut self, candidates: Vec<CompactionCandidate>, exit: &Exit) {
+        if self
+            .handle
+            .as_ref()
+            .is_some_and(|handle| !handle.is_finished())
+        {
+            return;
+        }
+
+        let exit = exit.clone();
+
+        self.handle = Some(thread::spawn(move || Self::pass(candidates, exit)));
+    }
+
+    fn pass(mut candidates: Vec<CompactionCandidate>, exit: Exit) {
+        candidates.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+
+        // Resume after the last compacted database so that restarts don't always compact the same ones
+        let cursor = fs::read_to_string(Self::cursor_path())
+            .ok()
+            .map(PathBuf::from);
+
+        let start = cursor.map_or(0, |cursor| {
+            candidates.partition_point(|candidate| candidate.path <= cursor)
+        });
+
+        candidates.rotate_left(start);
+
+        let mut compacted = 0;
+
+        for CompactionCandidate { path, compact } in candidates {
+            if exit.active() {
+                break;
+            }
+
+            if !Self::claim(&path) {
+                continue;
+            }
+
+            let result = compact(&path);
+
+            Self::release();
+
+            match result {
+                Ok(true) => compacted += 1,
+                Ok(false) => {}
+                Err(error) => {
+                    log(&format!("Failed to compact {path:?}: {error}"));
+                    continue;
+                }
+            }
+
+            let _ = fs::write(Self::cursor_path(), path.to_str().unwrap());
+        }
+
+        log(&format!("Compacted {compacted} databases"));
+    }
+
+    fn claim(path: &Path) -> bool {
+        let mut registry = REGISTRY.lock().unwrap();
+
+        if registry.open.contains(path) {
+            return false;
+        }
+
+        registry.compacting = Some(path.to_owned());
+
+        true
+    }
+
+    fn release() {
+        REGISTRY.lock().unwrap().compacting = None;
+
+        COMPACTED.notify_all();
+    }
+
+    fn cursor_path() -> PathBuf {
+        PathBuf::from(format!(
+            "{OUTPUTS_FOLDER_PATH}/databases/{CURSOR_FILE_NAME}"
+        ))
+    }
+}
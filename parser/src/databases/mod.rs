@@ -1,4 +1,7 @@
-use std::thread::{self};
+use std::{
+    path::{Path, PathBuf},
+    thread::{self},
+};
 
 use allocative::Allocative;
 
@@ -7,6 +10,9 @@ mod _trait;
 mod address_index_to_address_data;
 mod address_index_to_empty_address_data;
 mod address_to_address_index;
+mod backends;
+mod bench;
+mod compaction;
 mod metadata;
 mod txid_to_tx_data;
 mod txout_index_to_address_index;
@@ -17,6 +23,9 @@ use _trait::*;
 pub use address_index_to_address_data::*;
 pub use address_index_to_empty_address_data::*;
 pub use address_to_address_index::*;
+pub use backends::*;
+pub use bench::*;
+use compaction::*;
 use itertools::Itertools;
 use metadata::*;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
@@ -25,6 +34,7 @@ pub use txout_index_to_address_index::*;
 pub use txout_index_to_amount::*;
 
 use crate::{
+    io::OUTPUTS_FOLDER_PATH,
     log,
     structs::{Date, Height},
     utils::time,
@@ -39,6 +49,8 @@ pub struct Databases {
     pub txid_to_tx_data: TxidToTxData,
     pub txout_index_to_address_index: TxoutIndexToAddressIndex,
     pub txout_index_to_amount: TxoutIndexToAmount,
+    #[allocative(skip)]
+    compaction: Compaction,
 }
 
 impl Databases {
@@ -62,6 +74,7 @@ impl Databases {
             txid_to_tx_data,
             txout_index_to_address_index,
             txout_index_to_amount,
+            compaction: Compaction::default(),
         }
     }
 
@@ -91,10 +104,31 @@ impl Databases {
         Ok(())
     }
 
-    pub fn export(&mut self, height: Height, date: Date) -> color_eyre::Result<()> {
+    /// Exports the metadata and the write-ahead logs, the returned databases need to be committed once the checkpoint is
+    pub fn export(
+        &mut self,
+        height: Height,
+        date: Date,
+        record: bool,
+    ) -> color_eyre::Result<Vec<Box<dyn AnyDatabase + Send>>> {
         self.export_metadata(height, date)?;
 
+        let recording_folder = Path::new(RECORDING_FOLDER_PATH).join(height.to_string());
+
         self.drain_to_vec()
+            .into_par_iter()
+            .map(|database| {
+                if record {
+                    database.record(&recording_folder)?;
+                }
+
+                database.write_ahead_log().map(|_| database)
+            })
+            .collect()
+    }
+
+    pub fn commit(databases: Vec<Box<dyn AnyDatabase + Send>>) -> color_eyre::Result<()> {
+        databases
             .into_par_iter()
             .try_for_each(AnyDatabase::boxed_export)?;
 
@@ -147,6 +181,80 @@ impl Databases {
         exit.unblock();
     }
 
+    /// Starts a compaction pass over every database in the background if none is running
+    pub fn compact_in_background(&mut self, exit: &Exit) {
+        let candidates = AddressIndexToAddressData::compaction_candidates()
+            .into_iter()
+            .chain(AddressIndexToEmptyAddressData::compaction_candidates())
+            .chain(AddressToAddressIndex::compaction_candidates())
+            .chain(TxidToTxData::compaction_candidates())
+            .chain(TxoutIndexToAddressIndex::compaction_candidates())
+            .chain(TxoutIndexToAmount::compaction_candidates())
+            .collect_vec();
+
+        self.compaction.start(candidates, exit);
+    }
+
+    /// Replays the changes recorded with `--record-databases` with every backend and logs how they did
+    pub fn bench_backends(recording: &Path) -> color_eyre::Result<()> {
+        type BenchFn = fn(&Path, &[PathBuf]) -> color_eyre::Result<BenchResults>;
+
+        let groups: [(PathBuf, BenchFn); 6] = [
+            (
+                AddressIndexToAddressData::root(),
+                AddressIndexToAddressData::bench_db,
+            ),
+            (
+                AddressIndexToEmptyAddressData::root(),
+                AddressIndexToEmptyAddressData::bench_db,
+            ),
+            (
+                AddressToAddressIndex::root(),
+                AddressToAddressIndex::bench_db,
+            ),
+            (TxidToTxData::root(), TxidToTxData::bench_db),
+            (
+                TxoutIndexToAddressIndex::root(),
+                TxoutIndexToAddressIndex::bench_db,
+            ),
+            (TxoutIndexToAmount::root(), TxoutIndexToAmount::bench_db),
+        ];
+
+        let mut totals = BenchResults::new();
+
+        recorded_databases(recording)?.into_iter().try_for_each(
+            |(name, changes)| -> color_eyre::Result<()> {
+                // Reverse of `flat_name`
+                let path = Path::new(OUTPUTS_FOLDER_PATH).join(name.replace('-', "/"));
+
+                let Some((_, bench_db)) = groups.iter().find(|(root, _)| path.starts_with(root))
+                else {
+                    log(&format!("Skipping unknown database {name}"));
+                    return Ok(());
+                };
+
+                log(&format!("Benchmarking {name}..."));
+
+                bench_db(&path, &changes)?
+                    .into_iter()
+                    .for_each(|(backend, result)| *totals.entry(backend).or_default() += result);
+
+                Ok(())
+            },
+        )?;
+
+        totals.into_iter().for_each(|(backend, result)| {
+            log(&format!(
+                "{backend}: writes {} seconds, reads {} seconds, {} MB on disk",
+                result.writes.as_secs_f32(),
+                result.reads.as_secs_f32(),
+                result.bytes / 1_000_000
+            ));
+        });
+
+        Ok(())
+    }
+
     pub fn reset(&mut self, include_addresses: bool) {
         if include_addresses {
             let _ = self.address_index_to_address_data.reset();
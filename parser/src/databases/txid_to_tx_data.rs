@@ -2,6 +2,7 @@ use std::{
     collections::BTreeMap,
     fs, mem,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
 };
 
 use allocative::Allocative;
@@ -10,7 +11,7 @@ use itertools::Itertools;
 
 use crate::structs::{Date, Height, TxData, U8x31};
 
-use super::{AnyDatabase, AnyDatabaseGroup, Database as _Database, Metadata};
+use super::{bench, AnyDatabase, AnyDatabaseGroup, BenchResults, Database as _Database, Metadata};
 
 type Key = U8x31;
 type Value = TxData;
@@ -53,7 +54,7 @@ impl TxidToTxData {
 
     /// Doesn't check if the database is open contrary to `safe_get` which does and opens if needed.
     /// Though it makes it easy to use with rayon
-    pub fn unsafe_get(&self, txid: &Txid) -> Option<&Value> {
+    pub fn unsafe_get(&self, txid: &Txid) -> Option<Value> {
         let txid_key = Self::txid_to_key(txid);
 
         let db_index = Self::db_index(txid);
@@ -142,6 +143,14 @@ impl AnyDatabaseGroup for TxidToTxData {
         self.metadata.reset();
     }
 
+    fn compact_db(path: &Path) -> color_eyre::Result<bool> {
+        Database::compact_if_fragmented(path)
+    }
+
+    fn bench_db(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults> {
+        bench::<Key, Value>(path, changes)
+    }
+
     fn folder<'a>() -> &'a str {
         "txid_to_tx_data"
     }
@@ -2,6 +2,7 @@ use std::{
     collections::BTreeMap,
     fs, mem,
     ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
 };
 
 use allocative::Allocative;
@@ -9,7 +10,7 @@ use itertools::Itertools;
 
 use crate::structs::{Amount, Date, Height, TxoutIndex};
 
-use super::{AnyDatabase, AnyDatabaseGroup, Database as _Database, Metadata};
+use super::{bench, AnyDatabase, AnyDatabaseGroup, BenchResults, Database as _Database, Metadata};
 
 type Key = TxoutIndex;
 type Value = Amount;
@@ -61,7 +62,7 @@ impl TxoutIndexToAmount {
 
     /// Doesn't check if the database is open contrary to `safe_get` which does and opens if needed
     /// Though it makes it easy to use with rayon.
-    pub fn unsafe_get(&self, key: &Key) -> Option<&Value> {
+    pub fn unsafe_get(&self, key: &Key) -> Option<Value> {
         let db_index = Self::db_index(key);
 
         self.get(&db_index).unwrap().get(key)
@@ -105,6 +106,14 @@ impl AnyDatabaseGroup for TxoutIndexToAmount {
         self.metadata.reset();
     }
 
+    fn compact_db(path: &Path) -> color_eyre::Result<bool> {
+        Database::compact_if_fragmented(path)
+    }
+
+    fn bench_db(path: &Path, changes: &[PathBuf]) -> color_eyre::Result<BenchResults> {
+        bench::<Key, Value>(path, changes)
+    }
+
     fn folder<'a>() -> &'a str {
         "txout_index_to_amount"
     }
@@ -4,11 +4,12 @@ use struct_iterable::Iterable;
 
 use crate::{
     datasets::{
-        cohort_metadata::AddressCohortMetadataDataset, ComputeData, DateRecapDataset, RatioDataset,
-        SubDataset,
+        cohort_metadata::AddressCohortMetadataDataset, ComputeData, DateRecapDataset,
+        DistributionSubDataset, LivelinessSubDataset, RatioDataset, StrategySubDataset, SubDataset,
     },
     structs::{
-        AnyBiMap, AnyDateMap, AnyHeightMap, AnyMap, BiMap, Date, Height, MapKind, Timestamp, OHLC,
+        AnyBiMap, AnyDateMap, AnyHeightMap, AnyMap, BiMap, Date, Distribution, Height, MapKind,
+        Timestamp, OHLC,
     },
     DateMap, HeightMap,
 };
@@ -287,6 +288,10 @@ pub trait AnyDataset: Iterable {
                 if map.kind() == kind {
                     v.push(map as &(dyn AnyDateMap + Send + Sync))
                 }
+            } else if let Some(map) = any.downcast_ref::<DateMap<Distribution>>() {
+                if map.kind() == kind {
+                    v.push(map as &(dyn AnyDateMap + Send + Sync))
+                }
             } else if let Some(dataset) = any.downcast_ref::<DateRecapDataset<u32>>() {
                 dataset.as_vec().into_iter().for_each(|map| {
                     if map.kind() == kind {
@@ -309,6 +314,12 @@ pub trait AnyDataset: Iterable {
                 dataset.as_vec().into_iter().for_each(|dataset| {
                     v.append(&mut dataset.to_kind_date_map_vec(kind));
                 });
+            } else if let Some(dataset) = any.downcast_ref::<DistributionSubDataset>() {
+                v.append(&mut dataset.to_kind_date_map_vec(kind));
+            } else if let Some(dataset) = any.downcast_ref::<LivelinessSubDataset>() {
+                v.append(&mut dataset.to_kind_date_map_vec(kind));
+            } else if let Some(dataset) = any.downcast_ref::<StrategySubDataset>() {
+                v.append(&mut dataset.to_kind_date_map_vec(kind));
             } else if let Some(dataset) = any.downcast_ref::<AddressCohortMetadataDataset>() {
                 match kind {
                     MapKind::Inserted => dataset.to_inserted_date_map_vec(),
@@ -405,6 +416,13 @@ pub trait AnyDataset: Iterable {
                     }
                 }
             }
+            any if any.is::<DateMap<Distribution>>() => {
+                if let Some(map) = any.downcast_mut::<DateMap<Distribution>>() {
+                    if map.kind() == kind {
+                        v.push(map as &mut dyn AnyDateMap);
+                    }
+                }
+            }
             any if any.is::<DateRecapDataset<u32>>() => {
                 if let Some(dataset) = any.downcast_mut::<DateRecapDataset<u32>>() {
                     dataset.as_mut_vec().into_iter().for_each(|map| {
@@ -439,6 +457,21 @@ pub trait AnyDataset: Iterable {
                     });
                 }
             }
+            any if any.is::<DistributionSubDataset>() => {
+                if let Some(dataset) = any.downcast_mut::<DistributionSubDataset>() {
+                    v.append(&mut dataset.to_kind_mut_date_map_vec(kind));
+                }
+            }
+            any if any.is::<LivelinessSubDataset>() => {
+                if let Some(dataset) = any.downcast_mut::<LivelinessSubDataset>() {
+                    v.append(&mut dataset.to_kind_mut_date_map_vec(kind));
+                }
+            }
+            any if any.is::<StrategySubDataset>() => {
+                if let Some(dataset) = any.downcast_mut::<StrategySubDataset>() {
+                    v.append(&mut dataset.to_kind_mut_date_map_vec(kind));
+                }
+            }
             any if any.is::<AddressCohortMetadataDataset>() => {
                 if let Some(dataset) = any.downcast_mut::<AddressCohortMetadataDataset>() {
                     match kind {
@@ -509,6 +542,8 @@ pub trait AnyDataset: Iterable {
                 dataset.as_vec().into_iter().for_each(|dataset| {
                     v.append(&mut dataset.to_kind_height_map_vec(kind));
                 });
+            } else if let Some(dataset) = any.downcast_ref::<LivelinessSubDataset>() {
+                v.append(&mut dataset.to_kind_height_map_vec(kind));
             } else if let Some(dataset) = any.downcast_ref::<AddressCohortMetadataDataset>() {
                 match kind {
                     MapKind::Inserted => dataset.to_inserted_height_map_vec(),
@@ -612,6 +647,11 @@ pub trait AnyDataset: Iterable {
                     });
                 }
             }
+            any if any.is::<LivelinessSubDataset>() => {
+                if let Some(dataset) = any.downcast_mut::<LivelinessSubDataset>() {
+                    v.append(&mut dataset.to_kind_mut_height_map_vec(kind));
+                }
+            }
             any if any.is::<AddressCohortMetadataDataset>() => {
                 if let Some(dataset) = any.downcast_mut::<AddressCohortMetadataDataset>() {
                     match kind {
@@ -876,6 +916,32 @@ pub trait AnyDataset: Iterable {
         });
     }
 
+    fn evict(&mut self) {
+        self.to_inserted_mut_height_map_vec()
+            .into_iter()
+            .for_each(|map| map.evict());
+
+        self.to_inserted_mut_date_map_vec()
+            .into_iter()
+            .for_each(|map| map.evict());
+
+        self.to_inserted_mut_bi_map_vec()
+            .into_iter()
+            .for_each(|d| d.as_any_mut_map().into_iter().for_each(|map| map.evict()));
+
+        self.to_computed_mut_height_map_vec()
+            .into_iter()
+            .for_each(|map| map.evict());
+
+        self.to_computed_mut_date_map_vec()
+            .into_iter()
+            .for_each(|map| map.evict());
+
+        self.to_computed_mut_bi_map_vec()
+            .into_iter()
+            .for_each(|d| d.as_any_mut_map().into_iter().for_each(|map| map.evict()));
+    }
+
     fn reset_computed(&self) {
         self.to_all_computed_date_map_vec()
             .iter()
@@ -0,0 +1,185 @@
+use std::collections::BTreeSet;
+
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::{
+    datasets::{AnyDataset, ComputeData, InsertData, MinInitialStates},
+    structs::{BiMap, Config, DateMap, HeightMap, MapKind},
+};
+
+///
+/// Number of addresses by kind of activity.
+///
+/// Daily active, sending, receiving and reused addresses count each address once per day,
+/// revived and emptied addresses are summed events.
+///
+/// Addresses seen for the first time are the `new_addresses` of the metadata dataset.
+///
+#[derive(Allocative, Iterable)]
+pub struct AddressActivityDataset {
+    min_initial_states: MinInitialStates,
+
+    // Inserted
+    pub active_addresses: HeightMap<u32>,
+    pub active_addresses_1d_sum: DateMap<u32>,
+    pub sending_addresses: HeightMap<u32>,
+    pub sending_addresses_1d_sum: DateMap<u32>,
+    pub receiving_addresses: HeightMap<u32>,
+    pub receiving_addresses_1d_sum: DateMap<u32>,
+    pub reused_addresses: HeightMap<u32>,
+    pub reused_addresses_1d_sum: DateMap<u32>,
+    pub revived_addresses: HeightMap<u32>,
+    pub revived_addresses_1d_sum: DateMap<u32>,
+    pub emptied_addresses: HeightMap<u32>,
+    pub emptied_addresses_1d_sum: DateMap<u32>,
+
+    // Not exported, the current date is always fully parsed before an export
+    sending_addresses_of_the_day: BTreeSet<u32>,
+    receiving_addresses_of_the_day: BTreeSet<u32>,
+    reused_addresses_of_the_day: BTreeSet<u32>,
+
+    // Computed
+    /// Share of the receiving addresses that had already received before
+    pub address_reuse_rate: BiMap<f32>,
+}
+
+impl AddressActivityDataset {
+    pub fn import(parent_path: &str, config: &Config) -> color_eyre::Result<Self> {
+        let f = |s: &str| format!("{parent_path}/{s}");
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // Inserted
+            active_addresses: HeightMap::new_bin(1, MapKind::Inserted, &f("active_addresses")),
+            active_addresses_1d_sum: DateMap::new_bin(
+                2,
+                MapKind::Inserted,
+                &f("active_addresses_1d_sum"),
+            ),
+            sending_addresses: HeightMap::new_bin(1, MapKind::Inserted, &f("sending_addresses")),
+            sending_addresses_1d_sum: DateMap::new_bin(
+                2,
+                MapKind::Inserted,
+                &f("sending_addresses_1d_sum"),
+            ),
+            receiving_addresses: HeightMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("receiving_addresses"),
+            ),
+            receiving_addresses_1d_sum: DateMap::new_bin(
+                2,
+                MapKind::Inserted,
+                &f("receiving_addresses_1d_sum"),
+            ),
+            reused_addresses: HeightMap::new_bin(1, MapKind::Inserted, &f("reused_addresses")),
+            reused_addresses_1d_sum: DateMap::new_bin(
+                2,
+                MapKind::Inserted,
+                &f("reused_addresses_1d_sum"),
+            ),
+            revived_addresses: HeightMap::new_bin(1, MapKind::Inserted, &f("revived_addresses")),
+            revived_addresses_1d_sum: DateMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("revived_addresses_1d_sum"),
+            ),
+            emptied_addresses: HeightMap::new_bin(1, MapKind::Inserted, &f("emptied_addresses")),
+            emptied_addresses_1d_sum: DateMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("emptied_addresses_1d_sum"),
+            ),
+
+            sending_addresses_of_the_day: BTreeSet::default(),
+            receiving_addresses_of_the_day: BTreeSet::default(),
+            reused_addresses_of_the_day: BTreeSet::default(),
+
+            // Computed
+            address_reuse_rate: BiMap::new_bin(2, MapKind::Computed, &f("address_reuse_rate")),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    pub fn insert(
+        &mut self,
+        &InsertData {
+            address_activity,
+            height,
+            date,
+            date_blocks_range,
+            is_date_last_block,
+            ..
+        }: &InsertData,
+    ) {
+        self.active_addresses
+            .insert(height, address_activity.active);
+        self.sending_addresses
+            .insert(height, address_activity.sending);
+        self.receiving_addresses
+            .insert(height, address_activity.receiving);
+        self.reused_addresses
+            .insert(height, address_activity.reused);
+        self.revived_addresses
+            .insert(height, address_activity.revived);
+        self.emptied_addresses
+            .insert(height, address_activity.emptied);
+
+        self.sending_addresses_of_the_day
+            .extend(&address_activity.sending_indexes);
+        self.receiving_addresses_of_the_day
+            .extend(&address_activity.receiving_indexes);
+        self.reused_addresses_of_the_day
+            .extend(&address_activity.reused_indexes);
+
+        if is_date_last_block {
+            let active = self
+                .sending_addresses_of_the_day
+                .union(&self.receiving_addresses_of_the_day)
+                .count();
+
+            self.active_addresses_1d_sum.insert(date, active as u32);
+            self.sending_addresses_1d_sum
+                .insert(date, self.sending_addresses_of_the_day.len() as u32);
+            self.receiving_addresses_1d_sum
+                .insert(date, self.receiving_addresses_of_the_day.len() as u32);
+            self.reused_addresses_1d_sum
+                .insert(date, self.reused_addresses_of_the_day.len() as u32);
+
+            self.sending_addresses_of_the_day.clear();
+            self.receiving_addresses_of_the_day.clear();
+            self.reused_addresses_of_the_day.clear();
+
+            self.revived_addresses_1d_sum
+                .insert(date, self.revived_addresses.sum_range(date_blocks_range));
+            self.emptied_addresses_1d_sum
+                .insert(date, self.emptied_addresses.sum_range(date_blocks_range));
+        }
+    }
+
+    pub fn compute(&mut self, &ComputeData { heights, dates, .. }: &ComputeData) {
+        self.address_reuse_rate.height.multi_insert_percentage(
+            heights,
+            &mut self.reused_addresses,
+            &mut self.receiving_addresses,
+        );
+
+        self.address_reuse_rate.date.multi_insert_percentage(
+            dates,
+            &mut self.reused_addresses_1d_sum,
+            &mut self.receiving_addresses_1d_sum,
+        );
+    }
+}
+
+impl AnyDataset for AddressActivityDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
@@ -34,7 +34,7 @@ impl CohortDataset {
             min_initial_states: MinInitialStates::default(),
             split,
             metadata: AddressCohortMetadataDataset::import(parent_path, &name, config)?,
-            subs: SubDataset::import(parent_path, &name, config)?,
+            subs: SubDataset::import(parent_path, &name, true, config)?,
         };
 
         s.min_initial_states
@@ -93,11 +93,13 @@ impl CohortDataset {
             .any(|sub| sub.input.needs_insert(height, date))
     }
 
-    // fn needs_insert_output(&self, insert_data: &InsertData) -> bool {
-    //     self.sub_datasets_vec()
-    //         .iter()
-    //         .any(|sub| sub.output.needs_insert(height, date))
-    // }
+    pub fn needs_insert_output(&self, height: Height, date: Date) -> bool {
+        self.sub_datasets_vec().iter().any(|sub| {
+            sub.output
+                .as_ref()
+                .is_some_and(|output| output.needs_insert(height, date))
+        })
+    }
 
     pub fn insert_realized_data(&mut self, insert_data: &InsertData) {
         let realized_state = insert_data
@@ -184,21 +186,18 @@ impl CohortDataset {
         self.subs.input.insert(insert_data, state);
     }
 
-    // fn insert_output_data(&mut self, insert_data: &InsertData) {
-    //     let state = insert_data
-    //         .address_cohorts_output_states
-    //         .as_ref()
-    //         .unwrap()
-    //         .get(&self.split)
-    //         .unwrap();
-
-    //     self.output.insert(insert_data, &state.all);
-    //     self.illiquid.output.insert(insert_data, &state.illiquid);
-    //     self.liquid.output.insert(insert_data, &state.liquid);
-    //     self.highly_liquid
-    //         .output
-    //         .insert(insert_data, &state.highly_liquid);
-    // }
+    fn insert_output_data(&mut self, insert_data: &InsertData) {
+        let state = insert_data
+            .address_cohorts_output_states
+            .as_ref()
+            .unwrap()
+            .get(&self.split)
+            .unwrap();
+
+        if let Some(output) = self.subs.output.as_mut() {
+            output.insert(insert_data, state);
+        }
+    }
 
     pub fn insert(&mut self, insert_data: &InsertData) {
         if !insert_data.compute_addresses {
@@ -253,9 +252,9 @@ impl CohortDataset {
             self.insert_input_data(insert_data);
         }
 
-        // if self.needs_insert_output(insert_data) {
-        //     self.insert_output_data(insert_data);
-        // }
+        if self.needs_insert_output(insert_data.height, insert_data.date) {
+            self.insert_output_data(insert_data);
+        }
     }
 
     // pub fn should_compute_metadata(&self, compute_data: &ComputeData) -> bool {
@@ -298,11 +297,13 @@ impl CohortDataset {
     //         .any(|sub| sub.input.should_compute(compute_data))
     // }
 
-    // fn should_compute_output(&self, compute_data: &ComputeData) -> bool {
-    //     self.sub_datasets_vec()
-    //         .iter()
-    //         .any(|sub| sub.output.should_compute(compute_data))
-    // }
+    fn should_compute_output(&self, compute_data: &ComputeData) -> bool {
+        self.sub_datasets_vec().iter().any(|sub| {
+            sub.output
+                .as_ref()
+                .is_some_and(|output| output.should_compute(compute_data))
+        })
+    }
 
     fn compute_supply_data(
         &mut self,
@@ -315,11 +316,13 @@ impl CohortDataset {
     fn compute_unrealized_data(
         &mut self,
         compute_data: &ComputeData,
+        closes: &mut BiMap<f32>,
         circulating_supply: &mut BiMap<f64>,
         market_cap: &mut BiMap<f32>,
     ) {
         self.subs.unrealized.compute(
             compute_data,
+            closes,
             &mut self.subs.supply.supply,
             circulating_supply,
             market_cap,
@@ -330,17 +333,25 @@ impl CohortDataset {
         self.subs.realized.compute(compute_data, market_cap);
     }
 
-    fn compute_capitalization_data(&mut self, compute_data: &ComputeData, closes: &mut BiMap<f32>) {
-        self.subs
-            .capitalization
-            .compute(compute_data, closes, &mut self.subs.supply.supply);
+    fn compute_capitalization_data(
+        &mut self,
+        compute_data: &ComputeData,
+        closes: &mut BiMap<f32>,
+        realized_cap: &mut BiMap<f32>,
+    ) {
+        self.subs.capitalization.compute(
+            compute_data,
+            closes,
+            &mut self.subs.supply.supply,
+            realized_cap,
+        );
     }
 
-    // fn compute_output_data(&mut self, compute_data: &ComputeData) {
-    //     self.all
-    //         .output
-    //         .compute(compute_data, &mut self.supply.total);
-    // }
+    fn compute_output_data(&mut self, compute_data: &ComputeData) {
+        if let Some(output) = self.subs.output.as_mut() {
+            output.compute(compute_data, &mut self.subs.supply.supply);
+        }
+    }
 
     pub fn compute(
         &mut self,
@@ -348,13 +359,14 @@ impl CohortDataset {
         closes: &mut BiMap<f32>,
         circulating_supply: &mut BiMap<f64>,
         market_cap: &mut BiMap<f32>,
+        realized_cap: &mut BiMap<f32>,
     ) {
         if self.should_compute_supply(compute_data) {
             self.compute_supply_data(compute_data, circulating_supply);
         }
 
         if self.should_compute_unrealized(compute_data) {
-            self.compute_unrealized_data(compute_data, circulating_supply, market_cap);
+            self.compute_unrealized_data(compute_data, closes, circulating_supply, market_cap);
         }
 
         if self.should_compute_realized(compute_data) {
@@ -363,12 +375,13 @@ impl CohortDataset {
 
         // MUST BE after compute_supply
         if self.should_compute_capitalization(compute_data) {
-            self.compute_capitalization_data(compute_data, closes);
+            self.compute_capitalization_data(compute_data, closes, realized_cap);
         }
 
-        // if self.should_compute_output(compute_data) {
-        //     self.compute_output_data(compute_data);
-        // }
+        // MUST BE after compute_supply
+        if self.should_compute_output(compute_data) {
+            self.compute_output_data(compute_data);
+        }
     }
 }
 
@@ -1,3 +1,4 @@
+mod activity;
 mod all_metadata;
 mod cohort;
 pub mod cohort_metadata;
@@ -12,7 +13,10 @@ use crate::{
     Date,
 };
 
-use self::{all_metadata::AllAddressesMetadataDataset, cohort::CohortDataset};
+use self::{
+    activity::AddressActivityDataset, all_metadata::AllAddressesMetadataDataset,
+    cohort::CohortDataset,
+};
 
 use super::{AnyDataset, AnyDatasets, ComputeData, InsertData, MinInitialStates};
 
@@ -22,6 +26,8 @@ pub struct AddressDatasets {
 
     metadata: AllAddressesMetadataDataset,
 
+    activity: AddressActivityDataset,
+
     pub cohorts: SplitByAddressCohort<CohortDataset>,
 }
 
@@ -45,6 +51,8 @@ impl AddressDatasets {
 
             metadata: AllAddressesMetadataDataset::import(parent_path, config)?,
 
+            activity: AddressActivityDataset::import(parent_path, config)?,
+
             cohorts: cohorts.unwrap(),
         };
 
@@ -57,6 +65,8 @@ impl AddressDatasets {
     pub fn insert(&mut self, insert_data: &InsertData) {
         self.metadata.insert(insert_data);
 
+        self.activity.insert(insert_data);
+
         self.cohorts
             .as_mut_vec()
             .into_iter()
@@ -138,14 +148,23 @@ impl AddressDatasets {
         closes: &mut BiMap<f32>,
         circulating_supply: &mut BiMap<f64>,
         market_cap: &mut BiMap<f32>,
+        realized_cap: &mut BiMap<f32>,
     ) {
         self.metadata.compute(compute_data);
 
+        self.activity.compute(compute_data);
+
         self.cohorts
             .as_mut_vec()
             .into_iter()
             .for_each(|(cohort, _)| {
-                cohort.compute(compute_data, closes, circulating_supply, market_cap)
+                cohort.compute(
+                    compute_data,
+                    closes,
+                    circulating_supply,
+                    market_cap,
+                    realized_cap,
+                )
             })
     }
 }
@@ -160,7 +179,10 @@ impl AnyDatasets for AddressDatasets {
             .as_vec()
             .into_iter()
             .map(|(d, _)| d as &(dyn AnyDataset + Send + Sync))
-            .chain(vec![&self.metadata as &(dyn AnyDataset + Send + Sync)])
+            .chain(vec![
+                &self.metadata as &(dyn AnyDataset + Send + Sync),
+                &self.activity as &(dyn AnyDataset + Send + Sync),
+            ])
             .collect_vec()
     }
 
@@ -169,7 +191,10 @@ impl AnyDatasets for AddressDatasets {
             .as_mut_vec()
             .into_iter()
             .map(|(d, _)| d as &mut dyn AnyDataset)
-            .chain(vec![&mut self.metadata as &mut dyn AnyDataset])
+            .chain(vec![
+                &mut self.metadata as &mut dyn AnyDataset,
+                &mut self.activity as &mut dyn AnyDataset,
+            ])
             .collect_vec()
     }
 }
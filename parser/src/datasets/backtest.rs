@@ -0,0 +1,81 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::structs::{BiMap, Config};
+
+use super::{AnyDataset, ComputeData, MinInitialStates, Schedule, StrategySubDataset};
+
+///
+/// Dollar cost averaging strategies.
+///
+/// Lump sum returns are the `price_*_total_return` of the price datasets.
+///
+#[derive(Allocative, Iterable)]
+pub struct BacktestDataset {
+    min_initial_states: MinInitialStates,
+
+    pub dca_daily: StrategySubDataset,
+    pub dca_weekly: StrategySubDataset,
+    pub dca_monthly: StrategySubDataset,
+    /// Buys every day when the market cap is below the realized cap
+    pub dca_daily_mvrv_below_1: StrategySubDataset,
+}
+
+impl BacktestDataset {
+    pub fn import(parent_path: &str, config: &Config) -> color_eyre::Result<Self> {
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            dca_daily: StrategySubDataset::import(parent_path, "dca_daily", config)?,
+            dca_weekly: StrategySubDataset::import(parent_path, "dca_weekly", config)?,
+            dca_monthly: StrategySubDataset::import(parent_path, "dca_monthly", config)?,
+            dca_daily_mvrv_below_1: StrategySubDataset::import(
+                parent_path,
+                "dca_daily_mvrv_below_1",
+                config,
+            )?,
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    pub fn compute(
+        &mut self,
+        compute_data: &ComputeData,
+        closes: &mut BiMap<f32>,
+        market_cap_to_realized_cap_ratio: &mut BiMap<f32>,
+    ) {
+        [
+            (&mut self.dca_daily, Schedule::Daily),
+            (&mut self.dca_weekly, Schedule::Weekly),
+            (&mut self.dca_monthly, Schedule::Monthly),
+        ]
+        .into_iter()
+        .filter(|(strategy, _)| strategy.should_compute(compute_data))
+        .for_each(|(strategy, schedule)| {
+            strategy.compute(compute_data, &mut closes.date, |date| {
+                schedule.is_trigger_date(date)
+            });
+        });
+
+        if self.dca_daily_mvrv_below_1.should_compute(compute_data) {
+            self.dca_daily_mvrv_below_1
+                .compute(compute_data, &mut closes.date, |date| {
+                    Schedule::Daily.is_trigger_date(date)
+                        && market_cap_to_realized_cap_ratio
+                            .date
+                            .get_or_import(date)
+                            .is_some_and(|mvrv| mvrv < 1.0)
+                });
+        }
+    }
+}
+
+impl AnyDataset for BacktestDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
@@ -3,17 +3,29 @@ use struct_iterable::Iterable;
 
 use crate::{
     datasets::AnyDataset,
-    structs::{Config, MapKind},
+    structs::{BiMap, Config, MapKind},
+    utils::ONE_YEAR_IN_DAYS,
     DateMap, HeightMap,
 };
 
-use super::{InsertData, MinInitialStates};
+use super::{ComputeData, InsertData, MinInitialStates};
 
 #[derive(Allocative, Iterable)]
 pub struct CoindaysDataset {
     min_initial_states: MinInitialStates,
+
+    // Inserted
     pub coindays_destroyed: HeightMap<f32>,
     pub coindays_destroyed_1d_sum: DateMap<f32>,
+    pub average_supply_age: BiMap<f32>,
+    /// Computed at the end of each day only, it needs to walk every block
+    pub median_supply_age: DateMap<f32>,
+
+    // Computed
+    pub dormancy: DateMap<f32>,
+    pub supply_adjusted_coindays_destroyed: DateMap<f32>,
+    pub supply_adjusted_coindays_destroyed_1y_sma: DateMap<f32>,
+    pub binary_coindays_destroyed: DateMap<u8>,
 }
 
 impl CoindaysDataset {
@@ -30,6 +42,26 @@ impl CoindaysDataset {
                 MapKind::Inserted,
                 &f("coindays_destroyed_1d_sum"),
             ),
+            average_supply_age: BiMap::new_bin(1, MapKind::Inserted, &f("average_supply_age")),
+            median_supply_age: DateMap::new_bin(1, MapKind::Inserted, &f("median_supply_age")),
+
+            // Computed
+            dormancy: DateMap::new_bin(1, MapKind::Computed, &f("dormancy")),
+            supply_adjusted_coindays_destroyed: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("supply_adjusted_coindays_destroyed"),
+            ),
+            supply_adjusted_coindays_destroyed_1y_sma: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("supply_adjusted_coindays_destroyed_1y_sma"),
+            ),
+            binary_coindays_destroyed: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("binary_coindays_destroyed"),
+            ),
         };
 
         s.min_initial_states
@@ -46,17 +78,78 @@ impl CoindaysDataset {
             date_blocks_range,
             is_date_last_block,
             date,
+            states,
+            timestamp,
             ..
         }: &InsertData,
     ) {
         self.coindays_destroyed
             .insert(height, satdays_destroyed.to_btc() as f32);
 
+        let average_supply_age = states.supply_totals.average_supply_age(timestamp);
+
+        self.average_supply_age
+            .height
+            .insert(height, average_supply_age);
+
         if is_date_last_block {
             self.coindays_destroyed_1d_sum
                 .insert(date, self.coindays_destroyed.sum_range(date_blocks_range));
+
+            self.average_supply_age
+                .date
+                .insert(date, average_supply_age);
+
+            self.median_supply_age.insert(
+                date,
+                states
+                    .date_data_vec
+                    .compute_median_supply_age(states.supply_totals.supply(), timestamp),
+            );
         }
     }
+
+    pub fn compute(
+        &mut self,
+        &ComputeData { dates, .. }: &ComputeData,
+        circulating_supply: &mut BiMap<f64>,
+        transaction_volume_1d_sum: &mut DateMap<f64>,
+    ) {
+        // Average age in days of the coins that moved
+        self.dormancy.multi_insert_divide(
+            dates,
+            &mut self.coindays_destroyed_1d_sum,
+            transaction_volume_1d_sum,
+        );
+
+        self.supply_adjusted_coindays_destroyed.multi_insert_divide(
+            dates,
+            &mut self.coindays_destroyed_1d_sum,
+            &mut circulating_supply.date,
+        );
+
+        self.supply_adjusted_coindays_destroyed_1y_sma
+            .multi_insert_simple_average(
+                dates,
+                &mut self.supply_adjusted_coindays_destroyed,
+                ONE_YEAR_IN_DAYS,
+            );
+
+        // 1 when more coindays than usual were destroyed
+        self.binary_coindays_destroyed
+            .multi_insert_complex_transform(
+                dates,
+                &mut self.supply_adjusted_coindays_destroyed,
+                |(supply_adjusted_coindays_destroyed, date, ..)| {
+                    let average = self
+                        .supply_adjusted_coindays_destroyed_1y_sma
+                        .get_or_import(date)
+                        .unwrap();
+
+                    (supply_adjusted_coindays_destroyed > average) as u8
+                },
+            );
+    }
 }
 
 impl AnyDataset for CoindaysDataset {
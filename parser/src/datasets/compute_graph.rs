@@ -0,0 +1,637 @@
+use std::{
+    collections::BTreeSet,
+    ops::{Deref, DerefMut},
+};
+
+use itertools::Itertools;
+
+use crate::structs::{BiMap, DateMap, Height, HeightMap, Timestamp};
+
+use super::{
+    AddressDatasets, AllDatasets, AnyDataset, BacktestDataset, BlockMetadataDataset,
+    CoindaysDataset, CointimeDataset, ComputeData, ConstantDataset, DateMetadataDataset,
+    IndicatorsDataset, MiningDataset, PriceDatasets, TransactionDataset, UTXODatasets,
+};
+
+/// A dataset (or group of datasets) with its own `compute` function
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ComputeNode {
+    Constant,
+    Mining,
+    Price,
+    Transaction,
+    Coindays,
+    Address,
+    UTXO,
+    Cointime,
+    Indicators,
+    Backtest,
+}
+
+/// Maps that are read by the compute functions of other datasets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComputeInput {
+    FirstHeight,
+    LastHeight,
+    BlockTimestamp,
+    Close,
+    MarketCap,
+    CirculatingSupply,
+    BlockInterval,
+    InflationRate,
+    YearlyInflationRate,
+    CumulativeSubsidyInDollars,
+    RealizedCap,
+    RealizedPrice,
+    AnnualizedTransactionVolume,
+    TransactionVolume,
+    Liveliness,
+    UpTo1wRealizedCap,
+    From1yTo2yRealizedCap,
+    MarketCapToRealizedCapRatio,
+}
+
+impl ComputeInput {
+    /// Inserted while parsing, thus ready before any compute
+    fn is_inserted(self) -> bool {
+        matches!(
+            self,
+            Self::FirstHeight
+                | Self::LastHeight
+                | Self::BlockTimestamp
+                | Self::BlockInterval
+                | Self::RealizedCap
+                | Self::TransactionVolume
+                | Self::UpTo1wRealizedCap
+                | Self::From1yTo2yRealizedCap
+        )
+    }
+
+    /// Node whose dataset holds the map, `None` for the metadata datasets which have no compute
+    fn owned_by(self) -> Option<ComputeNode> {
+        match self {
+            Self::FirstHeight | Self::LastHeight | Self::BlockTimestamp => None,
+            Self::Close | Self::MarketCap => Some(ComputeNode::Price),
+            Self::CirculatingSupply
+            | Self::BlockInterval
+            | Self::InflationRate
+            | Self::YearlyInflationRate
+            | Self::CumulativeSubsidyInDollars => Some(ComputeNode::Mining),
+            Self::RealizedCap | Self::RealizedPrice => Some(ComputeNode::Address),
+            Self::AnnualizedTransactionVolume | Self::TransactionVolume => {
+                Some(ComputeNode::Transaction)
+            }
+            Self::Liveliness => Some(ComputeNode::Cointime),
+            Self::UpTo1wRealizedCap | Self::From1yTo2yRealizedCap => Some(ComputeNode::UTXO),
+            Self::MarketCapToRealizedCapRatio => Some(ComputeNode::Indicators),
+        }
+    }
+
+    /// Index of the node of the level that borrows the map, the other readers get a copy
+    ///
+    /// `None` when the dataset holding the map is itself computed in that level
+    fn borrower(self, level: &[ComputeNode]) -> Option<usize> {
+        if self.owned_by().is_some_and(|owner| level.contains(&owner)) {
+            return None;
+        }
+
+        level.iter().position(|node| node.inputs().contains(&self))
+    }
+
+    fn copy_into(self, datasets: &AllDatasets, sources: &mut ComputeSources) {
+        let capitalization = &datasets.address.cohorts.all.subs.capitalization;
+        let utxo_cohorts = &datasets.utxo.cohorts;
+
+        match self {
+            Self::FirstHeight => {
+                sources.first_height = Source::copy(&datasets.date_metadata.first_height)
+            }
+            Self::LastHeight => {
+                sources.last_height = Source::copy(&datasets.date_metadata.last_height)
+            }
+            Self::BlockTimestamp => {
+                sources.block_timestamp = Source::copy(&datasets.block_metadata.timestamp)
+            }
+            Self::Close => sources.closes = Source::copy(&datasets.price.close),
+            Self::MarketCap => sources.market_cap = Source::copy(&datasets.price.market_cap),
+            Self::CirculatingSupply => {
+                sources.circulating_supply = Source::copy(&datasets.mining.cumulative_subsidy)
+            }
+            Self::BlockInterval => {
+                sources.block_interval = Source::copy(&datasets.mining.block_interval)
+            }
+            Self::InflationRate => {
+                sources.inflation_rate = Source::copy(&datasets.mining.inflation_rate)
+            }
+            Self::YearlyInflationRate => {
+                sources.yearly_inflation_rate = Source::copy(&datasets.mining.yearly_inflation_rate)
+            }
+            Self::CumulativeSubsidyInDollars => {
+                sources.cumulative_subsidy_in_dollars =
+                    Source::copy(&datasets.mining.cumulative_subsidy_in_dollars)
+            }
+            Self::RealizedCap => sources.realized_cap = Source::copy(&capitalization.realized_cap),
+            Self::RealizedPrice => {
+                sources.realized_price = Source::copy(&capitalization.realized_price)
+            }
+            Self::AnnualizedTransactionVolume => {
+                sources.annualized_transaction_volume =
+                    Source::copy(&datasets.transaction.annualized_volume)
+            }
+            Self::TransactionVolume => {
+                sources.transaction_volume_1d_sum =
+                    Source::copy(&datasets.transaction.volume_1d_sum)
+            }
+            Self::Liveliness => sources.liveliness = Source::copy(&datasets.cointime.liveliness),
+            Self::UpTo1wRealizedCap => {
+                sources.up_to_1w_realized_cap =
+                    Source::copy(&utxo_cohorts.up_to_1w.subs.capitalization.realized_cap)
+            }
+            Self::From1yTo2yRealizedCap => {
+                sources.from_1y_to_2y_realized_cap =
+                    Source::copy(&utxo_cohorts.from_1y_to_2y.subs.capitalization.realized_cap)
+            }
+            Self::MarketCapToRealizedCapRatio => {
+                sources.market_cap_to_realized_cap_ratio =
+                    Source::copy(&datasets.indicators.market_cap_to_realized_cap_ratio)
+            }
+        }
+    }
+}
+
+impl ComputeNode {
+    const ALL: [Self; 10] = [
+        Self::Constant,
+        Self::Mining,
+        Self::Price,
+        Self::Transaction,
+        Self::Coindays,
+        Self::Address,
+        Self::UTXO,
+        Self::Cointime,
+        Self::Indicators,
+        Self::Backtest,
+    ];
+
+    fn inputs(self) -> &'static [ComputeInput] {
+        use ComputeInput::*;
+
+        match self {
+            Self::Constant => &[],
+            Self::Mining => &[FirstHeight, LastHeight, BlockTimestamp],
+            Self::Price => &[CirculatingSupply, LastHeight],
+            Self::Transaction => &[CirculatingSupply, BlockInterval],
+            Self::Coindays => &[CirculatingSupply, TransactionVolume],
+            Self::Address | Self::UTXO => &[Close, CirculatingSupply, MarketCap, RealizedCap],
+            Self::Cointime => &[
+                FirstHeight,
+                LastHeight,
+                Close,
+                CirculatingSupply,
+                RealizedCap,
+                RealizedPrice,
+                InflationRate,
+                YearlyInflationRate,
+                AnnualizedTransactionVolume,
+                CumulativeSubsidyInDollars,
+            ],
+            Self::Indicators => &[
+                Close,
+                MarketCap,
+                RealizedCap,
+                Liveliness,
+                UpTo1wRealizedCap,
+                From1yTo2yRealizedCap,
+            ],
+            Self::Backtest => &[Close, MarketCapToRealizedCapRatio],
+        }
+    }
+
+    fn dependencies(self) -> BTreeSet<Self> {
+        self.inputs()
+            .iter()
+            .filter(|input| !input.is_inserted())
+            .filter_map(|input| input.owned_by())
+            .filter(|node| *node != self)
+            .collect()
+    }
+
+    /// Topologically sorted groups of nodes, the nodes of a group only depend on the ones of the previous groups and can thus run in parallel
+    pub fn levels() -> Vec<Vec<Self>> {
+        let mut done = BTreeSet::new();
+
+        let mut levels = vec![];
+
+        while done.len() < Self::ALL.len() {
+            let level = Self::ALL
+                .into_iter()
+                .filter(|node| !done.contains(node))
+                .filter(|node| node.dependencies().is_subset(&done))
+                .collect::<Vec<_>>();
+
+            if level.is_empty() {
+                panic!("Cycle in the compute graph");
+            }
+
+            done.extend(level.iter().copied());
+
+            levels.push(level);
+        }
+
+        levels
+    }
+}
+
+///
+/// A source map of a task.
+///
+/// Reading a map needs a `&mut` to import chunks, so a map is borrowed by a single task of a level and copied for the others.
+///
+pub enum Source<'a, T> {
+    Borrowed(&'a mut T),
+    Copied(T),
+}
+
+impl<T> Source<'_, T>
+where
+    T: Clone,
+{
+    fn copy(map: &T) -> Option<Self> {
+        Some(Self::Copied(map.clone()))
+    }
+}
+
+impl<T> Deref for Source<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Self::Borrowed(map) => map,
+            Self::Copied(map) => map,
+        }
+    }
+}
+
+impl<T> DerefMut for Source<'_, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        match self {
+            Self::Borrowed(map) => map,
+            Self::Copied(map) => map,
+        }
+    }
+}
+
+/// The input maps of a task, only the ones listed in `ComputeNode::inputs` are set
+#[derive(Default)]
+pub struct ComputeSources<'a> {
+    first_height: Option<Source<'a, DateMap<Height>>>,
+    last_height: Option<Source<'a, DateMap<Height>>>,
+    block_timestamp: Option<Source<'a, HeightMap<Timestamp>>>,
+    closes: Option<Source<'a, BiMap<f32>>>,
+    market_cap: Option<Source<'a, BiMap<f32>>>,
+    circulating_supply: Option<Source<'a, BiMap<f64>>>,
+    block_interval: Option<Source<'a, HeightMap<u32>>>,
+    inflation_rate: Option<Source<'a, DateMap<f64>>>,
+    yearly_inflation_rate: Option<Source<'a, DateMap<f64>>>,
+    cumulative_subsidy_in_dollars: Option<Source<'a, BiMap<f32>>>,
+    realized_cap: Option<Source<'a, BiMap<f32>>>,
+    realized_price: Option<Source<'a, BiMap<f32>>>,
+    annualized_transaction_volume: Option<Source<'a, DateMap<f32>>>,
+    transaction_volume_1d_sum: Option<Source<'a, DateMap<f64>>>,
+    liveliness: Option<Source<'a, BiMap<f32>>>,
+    up_to_1w_realized_cap: Option<Source<'a, BiMap<f32>>>,
+    from_1y_to_2y_realized_cap: Option<Source<'a, BiMap<f32>>>,
+    market_cap_to_realized_cap_ratio: Option<Source<'a, BiMap<f32>>>,
+}
+
+/// Panics if the task reads a map that its node doesn't declare
+#[track_caller]
+fn declared<'b, T>(source: &'b mut Option<Source<'_, T>>) -> &'b mut T {
+    source
+        .as_deref_mut()
+        .expect("Input missing from ComputeNode::inputs")
+}
+
+/// A node with the dataset it computes
+#[allow(clippy::upper_case_acronyms)]
+pub enum ComputeTask<'a> {
+    Constant(&'a mut ConstantDataset),
+    Mining(&'a mut MiningDataset),
+    Price(&'a mut PriceDatasets),
+    Transaction(&'a mut TransactionDataset),
+    Coindays(&'a mut CoindaysDataset),
+    Address(&'a mut AddressDatasets),
+    UTXO(&'a mut UTXODatasets),
+    Cointime(&'a mut CointimeDataset),
+    Indicators(&'a mut IndicatorsDataset),
+    Backtest(&'a mut BacktestDataset),
+}
+
+impl<'a> ComputeTask<'a> {
+    /// The tasks of a level with their sources, see `ComputeInput::borrower`
+    pub fn split(
+        datasets: &'a mut AllDatasets,
+        level: &[ComputeNode],
+    ) -> Vec<(Self, ComputeSources<'a>)> {
+        let mut sources = level
+            .iter()
+            .enumerate()
+            .map(|(index, node)| {
+                let mut sources = ComputeSources::default();
+
+                node.inputs()
+                    .iter()
+                    .filter(|input| input.borrower(level) != Some(index))
+                    .for_each(|input| input.copy_into(datasets, &mut sources));
+
+                sources
+            })
+            .collect_vec();
+
+        let mut targets = ComputeTargets::new(datasets);
+
+        let tasks = level.iter().map(|node| targets.take(*node)).collect_vec();
+
+        targets.lend(level, &mut sources);
+
+        tasks.into_iter().zip(sources).collect()
+    }
+
+    pub fn run(self, compute_data: &ComputeData, mut sources: ComputeSources<'_>) {
+        match self {
+            Self::Constant(constant) => {
+                if constant.should_compute(compute_data) {
+                    constant.compute(compute_data);
+                }
+            }
+            Self::Mining(mining) => {
+                if mining.should_compute(compute_data) {
+                    mining.compute(
+                        compute_data,
+                        declared(&mut sources.first_height),
+                        declared(&mut sources.last_height),
+                        declared(&mut sources.block_timestamp),
+                    );
+                }
+            }
+            Self::Price(price) => price.compute(
+                compute_data,
+                declared(&mut sources.circulating_supply),
+                declared(&mut sources.last_height),
+            ),
+            Self::Transaction(transaction) => {
+                if transaction.should_compute(compute_data) {
+                    transaction.compute(
+                        compute_data,
+                        declared(&mut sources.circulating_supply),
+                        declared(&mut sources.block_interval),
+                    );
+                }
+            }
+            Self::Coindays(coindays) => {
+                if coindays.should_compute(compute_data) {
+                    coindays.compute(
+                        compute_data,
+                        declared(&mut sources.circulating_supply),
+                        declared(&mut sources.transaction_volume_1d_sum),
+                    );
+                }
+            }
+            Self::Address(address) => address.compute(
+                compute_data,
+                declared(&mut sources.closes),
+                declared(&mut sources.circulating_supply),
+                declared(&mut sources.market_cap),
+                declared(&mut sources.realized_cap),
+            ),
+            Self::UTXO(utxo) => utxo.compute(
+                compute_data,
+                declared(&mut sources.closes),
+                declared(&mut sources.circulating_supply),
+                declared(&mut sources.market_cap),
+                declared(&mut sources.realized_cap),
+            ),
+            Self::Cointime(cointime) => {
+                if cointime.should_compute(compute_data) {
+                    cointime.compute(
+                        compute_data,
+                        declared(&mut sources.first_height),
+                        declared(&mut sources.last_height),
+                        declared(&mut sources.closes),
+                        declared(&mut sources.circulating_supply),
+                        declared(&mut sources.realized_cap),
+                        declared(&mut sources.realized_price),
+                        declared(&mut sources.inflation_rate),
+                        declared(&mut sources.yearly_inflation_rate),
+                        declared(&mut sources.annualized_transaction_volume),
+                        declared(&mut sources.cumulative_subsidy_in_dollars),
+                    );
+                }
+            }
+            Self::Indicators(indicators) => {
+                if indicators.should_compute(compute_data) {
+                    indicators.compute(
+                        compute_data,
+                        declared(&mut sources.closes),
+                        declared(&mut sources.market_cap),
+                        declared(&mut sources.realized_cap),
+                        declared(&mut sources.liveliness),
+                        declared(&mut sources.up_to_1w_realized_cap),
+                        declared(&mut sources.from_1y_to_2y_realized_cap),
+                    );
+                }
+            }
+            Self::Backtest(backtest) => backtest.compute(
+                compute_data,
+                declared(&mut sources.closes),
+                declared(&mut sources.market_cap_to_realized_cap_ratio),
+            ),
+        }
+    }
+}
+
+/// Hands out each dataset at most once so that the tasks of a level can borrow them mutably at the same time
+struct ComputeTargets<'a> {
+    date_metadata: &'a mut DateMetadataDataset,
+    block_metadata: &'a mut BlockMetadataDataset,
+    constant: Option<&'a mut ConstantDataset>,
+    mining: Option<&'a mut MiningDataset>,
+    price: Option<&'a mut PriceDatasets>,
+    transaction: Option<&'a mut TransactionDataset>,
+    coindays: Option<&'a mut CoindaysDataset>,
+    address: Option<&'a mut AddressDatasets>,
+    utxo: Option<&'a mut UTXODatasets>,
+    cointime: Option<&'a mut CointimeDataset>,
+    indicators: Option<&'a mut IndicatorsDataset>,
+    backtest: Option<&'a mut BacktestDataset>,
+}
+
+impl<'a> ComputeTargets<'a> {
+    fn new(datasets: &'a mut AllDatasets) -> Self {
+        let AllDatasets {
+            date_metadata,
+            block_metadata,
+            constant,
+            mining,
+            price,
+            transaction,
+            coindays,
+            address,
+            utxo,
+            cointime,
+            indicators,
+            backtest,
+            ..
+        } = datasets;
+
+        Self {
+            date_metadata,
+            block_metadata,
+            constant: Some(constant),
+            mining: Some(mining),
+            price: Some(price),
+            transaction: Some(transaction),
+            coindays: Some(coindays),
+            address: Some(address),
+            utxo: Some(utxo),
+            cointime: Some(cointime),
+            indicators: Some(indicators),
+            backtest: Some(backtest),
+        }
+    }
+
+    fn take(&mut self, node: ComputeNode) -> ComputeTask<'a> {
+        let error = "Node taken twice";
+
+        match node {
+            ComputeNode::Constant => ComputeTask::Constant(self.constant.take().expect(error)),
+            ComputeNode::Mining => ComputeTask::Mining(self.mining.take().expect(error)),
+            ComputeNode::Price => ComputeTask::Price(self.price.take().expect(error)),
+            ComputeNode::Transaction => {
+                ComputeTask::Transaction(self.transaction.take().expect(error))
+            }
+            ComputeNode::Coindays => ComputeTask::Coindays(self.coindays.take().expect(error)),
+            ComputeNode::Address => ComputeTask::Address(self.address.take().expect(error)),
+            ComputeNode::UTXO => ComputeTask::UTXO(self.utxo.take().expect(error)),
+            ComputeNode::Cointime => ComputeTask::Cointime(self.cointime.take().expect(error)),
+            ComputeNode::Indicators => {
+                ComputeTask::Indicators(self.indicators.take().expect(error))
+            }
+            ComputeNode::Backtest => ComputeTask::Backtest(self.backtest.take().expect(error)),
+        }
+    }
+
+    /// Lends the maps of the datasets that weren't taken to their borrower
+    fn lend(self, level: &[ComputeNode], sources: &mut [ComputeSources<'a>]) {
+        use ComputeInput::*;
+
+        let Self {
+            date_metadata,
+            block_metadata,
+            mining,
+            price,
+            transaction,
+            address,
+            utxo,
+            cointime,
+            indicators,
+            ..
+        } = self;
+
+        let borrower = |input: ComputeInput| input.borrower(level);
+
+        if let Some(index) = borrower(FirstHeight) {
+            sources[index].first_height = Some(Source::Borrowed(&mut date_metadata.first_height));
+        }
+        if let Some(index) = borrower(LastHeight) {
+            sources[index].last_height = Some(Source::Borrowed(&mut date_metadata.last_height));
+        }
+        if let Some(index) = borrower(BlockTimestamp) {
+            sources[index].block_timestamp = Some(Source::Borrowed(&mut block_metadata.timestamp));
+        }
+
+        if let Some(price) = price {
+            if let Some(index) = borrower(Close) {
+                sources[index].closes = Some(Source::Borrowed(&mut price.close));
+            }
+            if let Some(index) = borrower(MarketCap) {
+                sources[index].market_cap = Some(Source::Borrowed(&mut price.market_cap));
+            }
+        }
+
+        if let Some(mining) = mining {
+            if let Some(index) = borrower(CirculatingSupply) {
+                sources[index].circulating_supply =
+                    Some(Source::Borrowed(&mut mining.cumulative_subsidy));
+            }
+            if let Some(index) = borrower(BlockInterval) {
+                sources[index].block_interval = Some(Source::Borrowed(&mut mining.block_interval));
+            }
+            if let Some(index) = borrower(InflationRate) {
+                sources[index].inflation_rate = Some(Source::Borrowed(&mut mining.inflation_rate));
+            }
+            if let Some(index) = borrower(YearlyInflationRate) {
+                sources[index].yearly_inflation_rate =
+                    Some(Source::Borrowed(&mut mining.yearly_inflation_rate));
+            }
+            if let Some(index) = borrower(CumulativeSubsidyInDollars) {
+                sources[index].cumulative_subsidy_in_dollars =
+                    Some(Source::Borrowed(&mut mining.cumulative_subsidy_in_dollars));
+            }
+        }
+
+        if let Some(address) = address {
+            let capitalization = &mut address.cohorts.all.subs.capitalization;
+
+            if let Some(index) = borrower(RealizedCap) {
+                sources[index].realized_cap =
+                    Some(Source::Borrowed(&mut capitalization.realized_cap));
+            }
+            if let Some(index) = borrower(RealizedPrice) {
+                sources[index].realized_price =
+                    Some(Source::Borrowed(&mut capitalization.realized_price));
+            }
+        }
+
+        if let Some(transaction) = transaction {
+            if let Some(index) = borrower(AnnualizedTransactionVolume) {
+                sources[index].annualized_transaction_volume =
+                    Some(Source::Borrowed(&mut transaction.annualized_volume));
+            }
+            if let Some(index) = borrower(TransactionVolume) {
+                sources[index].transaction_volume_1d_sum =
+                    Some(Source::Borrowed(&mut transaction.volume_1d_sum));
+            }
+        }
+
+        if let Some(cointime) = cointime {
+            if let Some(index) = borrower(Liveliness) {
+                sources[index].liveliness = Some(Source::Borrowed(&mut cointime.liveliness));
+            }
+        }
+
+        if let Some(utxo) = utxo {
+            let cohorts = &mut utxo.cohorts;
+
+            if let Some(index) = borrower(UpTo1wRealizedCap) {
+                sources[index].up_to_1w_realized_cap = Some(Source::Borrowed(
+                    &mut cohorts.up_to_1w.subs.capitalization.realized_cap,
+                ));
+            }
+            if let Some(index) = borrower(From1yTo2yRealizedCap) {
+                sources[index].from_1y_to_2y_realized_cap = Some(Source::Borrowed(
+                    &mut cohorts.from_1y_to_2y.subs.capitalization.realized_cap,
+                ));
+            }
+        }
+
+        if let Some(indicators) = indicators {
+            if let Some(index) = borrower(MarketCapToRealizedCapRatio) {
+                sources[index].market_cap_to_realized_cap_ratio = Some(Source::Borrowed(
+                    &mut indicators.market_cap_to_realized_cap_ratio,
+                ));
+            }
+        }
+    }
+}
@@ -11,7 +11,9 @@ pub struct ConstantDataset {
 
     pub _0: BiMap<u16>,
     pub _1: BiMap<u16>,
+    pub _25: BiMap<u16>,
     pub _50: BiMap<u16>,
+    pub _75: BiMap<u16>,
     pub _100: BiMap<u16>,
 }
 
@@ -25,7 +27,9 @@ impl ConstantDataset {
             // Computed
             _0: BiMap::new_bin(1, MapKind::Computed, &f("0")),
             _1: BiMap::new_bin(1, MapKind::Computed, &f("1")),
+            _25: BiMap::new_bin(1, MapKind::Computed, &f("25")),
             _50: BiMap::new_bin(1, MapKind::Computed, &f("50")),
+            _75: BiMap::new_bin(1, MapKind::Computed, &f("75")),
             _100: BiMap::new_bin(1, MapKind::Computed, &f("100")),
         };
 
@@ -38,7 +42,9 @@ impl ConstantDataset {
     pub fn compute(&mut self, &ComputeData { heights, dates, .. }: &ComputeData) {
         self._0.multi_insert_const(heights, dates, 0);
         self._1.multi_insert_const(heights, dates, 1);
+        self._25.multi_insert_const(heights, dates, 25);
         self._50.multi_insert_const(heights, dates, 50);
+        self._75.multi_insert_const(heights, dates, 75);
         self._100.multi_insert_const(heights, dates, 100);
     }
 }
@@ -47,6 +47,46 @@ impl DateMetadataDataset {
 
         self.last_height.insert(date, height);
     }
+
+    /// Each date should start right after the previous one ended
+    pub fn verify(&self) -> Vec<String> {
+        let first_heights = self.first_height.import_all_entries();
+        let last_heights = self.last_height.import_all_entries();
+
+        let mut issues = vec![];
+
+        let mut previous_last_height: Option<Height> = None;
+
+        first_heights.iter().for_each(|(date, first_height)| {
+            let Some(last_height) = last_heights.get(date) else {
+                issues.push(format!("{date}: missing last height"));
+                return;
+            };
+
+            if first_height > last_height {
+                issues.push(format!(
+                    "{date}: first height {first_height} is after last height {last_height}"
+                ));
+            }
+
+            if let Some(previous_last_height) = previous_last_height {
+                if **first_height != *previous_last_height + 1 {
+                    issues.push(format!(
+                        "{date}: first height {first_height} doesn't follow previous last height {previous_last_height}"
+                    ));
+                }
+            }
+
+            previous_last_height.replace(*last_height);
+        });
+
+        last_heights
+            .keys()
+            .filter(|date| !first_heights.contains_key(date))
+            .for_each(|date| issues.push(format!("{date}: missing first height")));
+
+        issues
+    }
 }
 
 impl AnyDataset for DateMetadataDataset {
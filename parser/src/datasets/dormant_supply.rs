@@ -0,0 +1,122 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::{
+    datasets::AnyDataset,
+    structs::{BiMap, Config, Epoch, MapKind},
+};
+
+use super::{InsertData, MinInitialStates};
+
+///
+/// Supply that is likely lost.
+///
+/// Supply held in P2PK outputs and in addresses whose public key was revealed is in the `p2pk` and `revealed_pubkey` address cohorts.
+///
+#[derive(Allocative, Iterable)]
+pub struct DormantSupplyDataset {
+    min_initial_states: MinInitialStates,
+
+    // Inserted
+    pub epoch_1_from_10y_supply: BiMap<f64>,
+    pub epoch_2_from_10y_supply: BiMap<f64>,
+    pub epoch_3_from_10y_supply: BiMap<f64>,
+    pub epoch_4_from_10y_supply: BiMap<f64>,
+    pub epoch_5_from_10y_supply: BiMap<f64>,
+    /// Unspent supply of every output of all blocks up to height 54,316, not only the ones attributed to Satoshi
+    pub satoshi_era_supply: BiMap<f64>,
+}
+
+impl DormantSupplyDataset {
+    pub fn import(parent_path: &str, config: &Config) -> color_eyre::Result<Self> {
+        let f = |s: &str| format!("{parent_path}/{s}");
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // Inserted
+            epoch_1_from_10y_supply: BiMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("epoch_1_from_10y_supply"),
+            ),
+            epoch_2_from_10y_supply: BiMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("epoch_2_from_10y_supply"),
+            ),
+            epoch_3_from_10y_supply: BiMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("epoch_3_from_10y_supply"),
+            ),
+            epoch_4_from_10y_supply: BiMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("epoch_4_from_10y_supply"),
+            ),
+            epoch_5_from_10y_supply: BiMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("epoch_5_from_10y_supply"),
+            ),
+            satoshi_era_supply: BiMap::new_bin(1, MapKind::Inserted, &f("satoshi_era_supply")),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    pub fn insert(
+        &mut self,
+        &InsertData {
+            height,
+            date,
+            is_date_last_block,
+            states,
+            ..
+        }: &InsertData,
+    ) {
+        let supply_totals = &states.supply_totals;
+
+        [
+            &mut self.epoch_1_from_10y_supply,
+            &mut self.epoch_2_from_10y_supply,
+            &mut self.epoch_3_from_10y_supply,
+            &mut self.epoch_4_from_10y_supply,
+            &mut self.epoch_5_from_10y_supply,
+        ]
+        .into_iter()
+        .enumerate()
+        .for_each(|(index, supply)| {
+            let epoch = Epoch(index as u16 + 1);
+
+            let amount = supply
+                .height
+                .insert(height, supply_totals.supply_from_10y(&epoch).to_btc());
+
+            if is_date_last_block {
+                supply.date.insert(date, amount);
+            }
+        });
+
+        let satoshi_era_supply = self
+            .satoshi_era_supply
+            .height
+            .insert(height, supply_totals.satoshi_era_supply().to_btc());
+
+        if is_date_last_block {
+            self.satoshi_era_supply
+                .date
+                .insert(date, satoshi_era_supply);
+        }
+    }
+}
+
+impl AnyDataset for DormantSupplyDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
@@ -0,0 +1,139 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::{
+    structs::{BiMap, Config, DateMap, MapKind},
+    utils::ONE_YEAR_IN_DAYS,
+};
+
+use super::{AnyDataset, ComputeData, MinInitialStates};
+
+#[derive(Allocative, Iterable)]
+pub struct IndicatorsDataset {
+    min_initial_states: MinInitialStates,
+
+    // Computed
+    pub market_cap_to_realized_cap_ratio: BiMap<f32>,
+    pub market_cap_minus_realized_cap: DateMap<f32>,
+    pub market_cap_4y_standard_deviation: DateMap<f32>,
+    pub market_cap_to_realized_cap_z_score: DateMap<f32>,
+    pub hodl_opportunity_cost: DateMap<f32>,
+    pub hodl_bank: DateMap<f32>,
+    pub reserve_risk: DateMap<f32>,
+    pub rhodl_ratio: BiMap<f32>,
+}
+
+impl IndicatorsDataset {
+    pub fn import(parent_path: &str, config: &Config) -> color_eyre::Result<Self> {
+        let f = |s: &str| format!("{parent_path}/{s}");
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // Computed
+            market_cap_to_realized_cap_ratio: BiMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("market_cap_to_realized_cap_ratio"),
+            ),
+            market_cap_minus_realized_cap: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("market_cap_minus_realized_cap"),
+            ),
+            market_cap_4y_standard_deviation: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("market_cap_4y_standard_deviation"),
+            ),
+            market_cap_to_realized_cap_z_score: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("market_cap_to_realized_cap_z_score"),
+            ),
+            hodl_opportunity_cost: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("hodl_opportunity_cost"),
+            ),
+            hodl_bank: DateMap::new_bin(1, MapKind::Computed, &f("hodl_bank")),
+            reserve_risk: DateMap::new_bin(1, MapKind::Computed, &f("reserve_risk")),
+            rhodl_ratio: BiMap::new_bin(1, MapKind::Computed, &f("rhodl_ratio")),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn compute(
+        &mut self,
+        &ComputeData { heights, dates, .. }: &ComputeData,
+        closes: &mut BiMap<f32>,
+        market_cap: &mut BiMap<f32>,
+        realized_cap: &mut BiMap<f32>,
+        liveliness: &mut BiMap<f32>,
+        up_to_1w_realized_cap: &mut BiMap<f32>,
+        from_1y_to_2y_realized_cap: &mut BiMap<f32>,
+    ) {
+        // MVRV
+        self.market_cap_to_realized_cap_ratio.multi_insert_divide(
+            heights,
+            dates,
+            market_cap,
+            realized_cap,
+        );
+
+        self.market_cap_minus_realized_cap.multi_insert_subtract(
+            dates,
+            &mut market_cap.date,
+            &mut realized_cap.date,
+        );
+
+        self.market_cap_4y_standard_deviation
+            .multi_insert_standard_deviation(
+                dates,
+                &mut market_cap.date,
+                Some(4 * ONE_YEAR_IN_DAYS),
+            );
+
+        // MVRV-Z
+        self.market_cap_to_realized_cap_z_score.multi_insert_divide(
+            dates,
+            &mut self.market_cap_minus_realized_cap,
+            &mut self.market_cap_4y_standard_deviation,
+        );
+
+        // Value of the coins that weren't spent, weighted by how dormant the network is
+        self.hodl_opportunity_cost.multi_insert_complex_transform(
+            dates,
+            &mut closes.date,
+            |(close, date, ..)| {
+                let liveliness = liveliness.date.get_or_import(date).unwrap();
+
+                close * (1.0 - liveliness)
+            },
+        );
+
+        self.hodl_bank
+            .multi_insert_cumulative(dates, &mut self.hodl_opportunity_cost);
+
+        self.reserve_risk
+            .multi_insert_divide(dates, &mut closes.date, &mut self.hodl_bank);
+
+        self.rhodl_ratio.multi_insert_divide(
+            heights,
+            dates,
+            up_to_1w_realized_cap,
+            from_1y_to_2y_realized_cap,
+        );
+    }
+}
+
+impl AnyDataset for IndicatorsDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
@@ -5,10 +5,12 @@ use struct_iterable::Iterable;
 
 use crate::{
     datasets::AnyDataset,
-    structs::{Amount, BiMap, Config, DateMap, Height, HeightMap, MapKey, MapKind},
+    structs::{
+        Amount, BiMap, Config, Date, DateMap, Epoch, Height, HeightMap, MapKey, MapKind, Timestamp,
+    },
     utils::{
-        BYTES_IN_MB, ONE_DAY_IN_DAYS, ONE_MONTH_IN_DAYS, ONE_WEEK_IN_DAYS, ONE_YEAR_IN_DAYS,
-        TARGET_BLOCKS_PER_DAY,
+        BLOCKS_PER_DIFFICULTY_EPOCH, BYTES_IN_MB, ONE_DAY_IN_DAYS, ONE_DAY_IN_S, ONE_MONTH_IN_DAYS,
+        ONE_WEEK_IN_DAYS, ONE_YEAR_IN_DAYS, TARGET_BLOCKS_PER_DAY,
     },
 };
 
@@ -111,15 +113,18 @@ pub struct MiningDataset {
     pub block_vbytes_recap: DateRecapDataset<u64>,
     pub block_interval_recap: DateRecapDataset<u32>, // in s
     pub puell_multiple: DateMap<f32>,
+    pub block_interval_2w_sma: HeightMap<f32>, // in s
+    pub difficulty_epoch_average_block_interval: HeightMap<f32>, // in s
+    pub blocks_until_difficulty_adjustment: HeightMap<u32>,
+    pub days_until_difficulty_adjustment: HeightMap<f32>,
+    pub projected_difficulty_adjustment: HeightMap<f32>, // in %
+    pub blocks_until_halving: HeightMap<u32>,
+    pub days_until_halving: HeightMap<f32>,
+    pub estimated_halving_date: HeightMap<Date>,
     // pub hash_price_in_dollars: DateMap<f64>,
     // pub hash_price_30d_volatility: BiMap<f32>,
-    // difficulty_adjustment
-    // next_difficulty_adjustment
     // op return fees
     // inscriptions fees
-    // until adjustement
-    // until halving in days
-    // until halving in blocks
 }
 
 impl MiningDataset {
@@ -356,6 +361,42 @@ impl MiningDataset {
             hash_price_min: DateMap::new_bin(1, MapKind::Computed, &f("hash_price_min")),
             hash_price_rebound: DateMap::new_bin(1, MapKind::Computed, &f("hash_price_rebound")),
             puell_multiple: DateMap::new_bin(1, MapKind::Computed, &f("puell_multiple")),
+            block_interval_2w_sma: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("block_interval_2w_sma"),
+            ),
+            difficulty_epoch_average_block_interval: HeightMap::new_bin(
+                2,
+                MapKind::Computed,
+                &f("difficulty_epoch_average_block_interval"),
+            ),
+            blocks_until_difficulty_adjustment: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("blocks_until_difficulty_adjustment"),
+            ),
+            days_until_difficulty_adjustment: HeightMap::new_bin(
+                2,
+                MapKind::Computed,
+                &f("days_until_difficulty_adjustment"),
+            ),
+            projected_difficulty_adjustment: HeightMap::new_bin(
+                2,
+                MapKind::Computed,
+                &f("projected_difficulty_adjustment"),
+            ),
+            blocks_until_halving: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("blocks_until_halving"),
+            ),
+            days_until_halving: HeightMap::new_bin(2, MapKind::Computed, &f("days_until_halving")),
+            estimated_halving_date: HeightMap::new_bin(
+                2,
+                MapKind::Computed,
+                &f("estimated_halving_date"),
+            ),
         };
 
         s.min_initial_states
@@ -461,6 +502,7 @@ impl MiningDataset {
         &ComputeData { heights, dates, .. }: &ComputeData,
         first_height: &mut DateMap<Height>,
         last_height: &mut DateMap<Height>,
+        block_timestamp: &mut HeightMap<Timestamp>,
     ) {
         self.blocks_mined_1w_sum.multi_insert_last_x_sum(
             dates,
@@ -697,11 +739,6 @@ impl MiningDataset {
             &mut self.hash_price_min,
         );
 
-        self.puell_multiple.multi_insert_divide(
-            dates,
-            &mut self.coinbase_in_dollars_1d_sum,
-            &mut self.coinbase_in_dollars_1d_sum_1y_sma,
-        );
         self.puell_multiple.multi_insert_divide(
             dates,
             &mut self.coinbase_in_dollars_1d_sum,
@@ -714,6 +751,8 @@ impl MiningDataset {
             ONE_DAY_IN_DAYS,
         );
 
+        self.compute_projections(heights, block_timestamp);
+
         dates.iter().for_each(|date| {
             let first = first_height.get_or_import(date).unwrap();
             let last = last_height.get_or_import(date).unwrap();
@@ -751,6 +790,103 @@ impl MiningDataset {
             );
         })
     }
+
+    fn compute_projections(
+        &mut self,
+        heights: &[Height],
+        block_timestamp: &mut HeightMap<Timestamp>,
+    ) {
+        let blocks_until_multiple_of = |height: &Height, multiple: usize| {
+            ((height.to_usize() / multiple + 1) * multiple - height.to_usize()) as u32
+        };
+
+        let target_block_interval = (ONE_DAY_IN_S / TARGET_BLOCKS_PER_DAY) as f32;
+
+        self.block_interval_2w_sma.multi_insert_simple_average(
+            heights,
+            &mut self.block_interval,
+            2 * ONE_WEEK_IN_DAYS * TARGET_BLOCKS_PER_DAY,
+        );
+
+        // Like the protocol, uses the timestamps of the first block of the epoch and the current one
+        // since timestamps aren't monotonic, falls back to the previous average until time went forward
+        self.difficulty_epoch_average_block_interval
+            .multi_insert_complex_transform(
+                heights,
+                block_timestamp,
+                |(timestamp, height, block_timestamp, map)| {
+                    let blocks_into_epoch = height.to_usize() % BLOCKS_PER_DIFFICULTY_EPOCH;
+
+                    let epoch_first_timestamp = block_timestamp
+                        .get_or_import(&(*height - blocks_into_epoch))
+                        .unwrap();
+
+                    if blocks_into_epoch == 0 || timestamp <= epoch_first_timestamp {
+                        return height
+                            .checked_sub(1)
+                            .and_then(|previous_height| map.get_or_import(&previous_height))
+                            .unwrap_or(target_block_interval);
+                    }
+
+                    (*timestamp - *epoch_first_timestamp) as f32 / blocks_into_epoch as f32
+                },
+            );
+
+        self.blocks_until_difficulty_adjustment
+            .multi_insert(heights, |height| {
+                blocks_until_multiple_of(height, BLOCKS_PER_DIFFICULTY_EPOCH)
+            });
+
+        self.days_until_difficulty_adjustment
+            .multi_insert_complex_transform(
+                heights,
+                &mut self.blocks_until_difficulty_adjustment,
+                |(blocks, height, ..)| {
+                    let average_block_interval = self
+                        .difficulty_epoch_average_block_interval
+                        .get_or_import(height)
+                        .unwrap();
+
+                    blocks as f32 * average_block_interval / ONE_DAY_IN_S as f32
+                },
+            );
+
+        // Difficulty can be at most multiplied or divided by 4 per adjustment
+        self.projected_difficulty_adjustment
+            .multi_insert_simple_transform(
+                heights,
+                &mut self.difficulty_epoch_average_block_interval,
+                |average_block_interval, _| {
+                    ((target_block_interval / average_block_interval).clamp(0.25, 4.0) - 1.0)
+                        * 100.0
+                },
+            );
+
+        self.blocks_until_halving.multi_insert(heights, |height| {
+            blocks_until_multiple_of(height, Epoch::BLOCKS_PER_EPOCH)
+        });
+
+        self.days_until_halving.multi_insert_complex_transform(
+            heights,
+            &mut self.blocks_until_halving,
+            |(blocks, height, ..)| {
+                let average_block_interval =
+                    self.block_interval_2w_sma.get_or_import(height).unwrap();
+
+                blocks as f32 * average_block_interval / ONE_DAY_IN_S as f32
+            },
+        );
+
+        self.estimated_halving_date.multi_insert_complex_transform(
+            heights,
+            &mut self.days_until_halving,
+            |(days, height, ..)| {
+                let timestamp = block_timestamp.get_or_import(height).unwrap();
+
+                Timestamp::wrap(*timestamp + (days * ONE_DAY_IN_S as f32) as u32).to_date()
+            },
+        );
+    }
 }
 
 impl AnyDataset for MiningDataset {
@@ -1,4 +1,9 @@
-use std::{collections::BTreeMap, fs, ops::RangeInclusive, path::Path};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    ops::RangeInclusive,
+    path::Path,
+};
 
 use allocative::Allocative;
 
@@ -8,11 +13,15 @@ use rayon::prelude::*;
 
 mod _traits;
 mod address;
+mod backtest;
 mod block_metadata;
 mod coindays;
 mod cointime;
+mod compute_graph;
 mod constant;
 mod date_metadata;
+mod dormant_supply;
+mod indicators;
 mod mining;
 mod price;
 mod subs;
@@ -21,11 +30,15 @@ mod utxo;
 
 pub use _traits::*;
 pub use address::*;
+pub use backtest::*;
 pub use block_metadata::*;
 pub use coindays::*;
 pub use cointime::*;
+pub use compute_graph::*;
 pub use constant::*;
 pub use date_metadata::*;
+pub use dormant_supply::*;
+pub use indicators::*;
 pub use mining::*;
 pub use price::*;
 use serde_json::Value;
@@ -37,20 +50,22 @@ use crate::{
     databases::Databases,
     io::{Json, JSON_EXTENSION},
     states::{
-        AddressCohortsInputStates,
-        AddressCohortsOneShotStates,
-        AddressCohortsRealizedStates,
-        States,
-        UTXOCohortsOneShotStates,
-        // UTXOCohortsReceivedStates,
-        UTXOCohortsSentStates,
+        AddressCohortsInputStates, AddressCohortsOneShotStates, AddressCohortsOutputStates,
+        AddressCohortsRealizedStates, States, UTXOCohortsAgingStates, UTXOCohortsOneShotStates,
+        UTXOCohortsReceivedStates, UTXOCohortsSentStates,
+    },
+    structs::{
+        AddressActivity, Amount, AnyDateMap, AnyMap, Config, Date, Height, MapDependencies, MapKey,
+        Price, Timestamp,
     },
-    structs::{Amount, Config, Date, Height, Price, Timestamp},
+    utils::log,
 };
 
 pub struct InsertData<'a> {
+    pub address_activity: &'a AddressActivity,
     pub address_cohorts_input_states: &'a Option<AddressCohortsInputStates>,
     pub address_cohorts_one_shot_states: &'a Option<AddressCohortsOneShotStates>,
+    pub address_cohorts_output_states: &'a Option<AddressCohortsOutputStates>,
     pub address_cohorts_realized_states: &'a Option<AddressCohortsRealizedStates>,
     pub amount_sent: Amount,
     pub block_interval: Timestamp,
@@ -73,8 +88,9 @@ pub struct InsertData<'a> {
     pub states: &'a States,
     pub timestamp: Timestamp,
     pub transaction_count: usize,
+    pub utxo_cohorts_aging_states: &'a UTXOCohortsAgingStates,
     pub utxo_cohorts_one_shot_states: &'a UTXOCohortsOneShotStates,
-    // pub utxo_cohorts_received_states: &'a UTXOCohortsReceivedStates,
+    pub utxo_cohorts_received_states: &'a UTXOCohortsReceivedStates,
     pub utxo_cohorts_sent_states: &'a UTXOCohortsSentStates,
 }
 
@@ -89,17 +105,20 @@ pub struct AllDatasets {
 
     pub constant: ConstantDataset,
     pub address: AddressDatasets,
+    pub backtest: BacktestDataset,
     pub block_metadata: BlockMetadataDataset,
     pub coindays: CoindaysDataset,
     pub cointime: CointimeDataset,
     pub date_metadata: DateMetadataDataset,
+    pub dormant_supply: DormantSupplyDataset,
+    pub indicators: IndicatorsDataset,
     pub mining: MiningDataset,
     pub price: PriceDatasets,
     pub transaction: TransactionDataset,
     pub utxo: UTXODatasets,
 }
 
-const DATASETS_PATH: &str = "../datasets";
+pub const DATASETS_PATH: &str = "../datasets";
 
 impl AllDatasets {
     pub fn import(config: &Config) -> color_eyre::Result<Self> {
@@ -115,6 +134,12 @@ impl AllDatasets {
 
         let coindays = CoindaysDataset::import(path, config)?;
 
+        let dormant_supply = DormantSupplyDataset::import(path, config)?;
+
+        let indicators = IndicatorsDataset::import(path, config)?;
+
+        let backtest = BacktestDataset::import(path, config)?;
+
         let mining = MiningDataset::import(path, config)?;
 
         let block_metadata = BlockMetadataDataset::import(path, config)?;
@@ -129,11 +154,14 @@ impl AllDatasets {
             min_initial_states: MinInitialStates::default(),
 
             address,
+            backtest,
             block_metadata,
             cointime,
             coindays,
             constant,
             date_metadata,
+            dormant_supply,
+            indicators,
             price,
             mining,
             transaction,
@@ -176,6 +204,13 @@ impl AllDatasets {
             self.coindays.insert(&insert_data);
         }
 
+        if self
+            .dormant_supply
+            .needs_insert(insert_data.height, insert_data.date)
+        {
+            self.dormant_supply.insert(&insert_data);
+        }
+
         if self
             .mining
             .needs_insert(insert_data.height, insert_data.date)
@@ -199,35 +234,13 @@ impl AllDatasets {
     }
 
     pub fn compute(&mut self, compute_data: ComputeData) {
-        if self.constant.should_compute(&compute_data) {
-            self.constant.compute(&compute_data);
-        }
+        MapDependencies::clear_reads();
 
-        if self.mining.should_compute(&compute_data) {
-            self.mining.compute(
-                &compute_data,
-                &mut self.date_metadata.first_height,
-                &mut self.date_metadata.last_height,
-            );
-        }
-
-        // No compute needed for now
-        self.price
-            .compute(&compute_data, &mut self.mining.cumulative_subsidy);
-
-        self.address.compute(
-            &compute_data,
-            &mut self.price.close,
-            &mut self.mining.cumulative_subsidy,
-            &mut self.price.market_cap,
-        );
-
-        self.utxo.compute(
-            &compute_data,
-            &mut self.price.close,
-            &mut self.mining.cumulative_subsidy,
-            &mut self.price.market_cap,
-        );
+        ComputeNode::levels().into_iter().for_each(|level| {
+            ComputeTask::split(self, &level)
+                .into_par_iter()
+                .for_each(|(task, sources)| task.run(&compute_data, sources));
+        });
 
         // No compute needed for now
         // if self.block_metadata.should_compute(height, date) {
@@ -238,35 +251,6 @@ impl AllDatasets {
         // if self.date_metadata.should_compute(height, date) {
         //     self.date_metadata.compute(&compute_data);
         // }
-
-        // No compute needed for now
-        // if self.coindays.should_compute(height, date) {
-        //     self.coindays.compute(&compute_data);
-        // }
-
-        if self.transaction.should_compute(&compute_data) {
-            self.transaction.compute(
-                &compute_data,
-                &mut self.mining.cumulative_subsidy,
-                &mut self.mining.block_interval,
-            );
-        }
-
-        if self.cointime.should_compute(&compute_data) {
-            self.cointime.compute(
-                &compute_data,
-                &mut self.date_metadata.first_height,
-                &mut self.date_metadata.last_height,
-                &mut self.price.close,
-                &mut self.mining.cumulative_subsidy,
-                &mut self.address.cohorts.all.subs.capitalization.realized_cap,
-                &mut self.address.cohorts.all.subs.capitalization.realized_price,
-                &mut self.mining.inflation_rate,
-                &mut self.mining.yearly_inflation_rate,
-                &mut self.transaction.annualized_volume,
-                &mut self.mining.cumulative_subsidy_in_dollars,
-            );
-        }
     }
 
     pub fn export_meta_files(&self) -> color_eyre::Result<()> {
@@ -305,6 +289,13 @@ impl AllDatasets {
         Ok(())
     }
 
+    /// Frees the imported chunks that aren't needed to keep parsing, they'll be imported again if read
+    pub fn evict(&mut self) {
+        self.to_mut_any_dataset_vec()
+            .into_iter()
+            .for_each(|dataset| dataset.evict());
+    }
+
     pub fn export(&mut self) -> color_eyre::Result<()> {
         self.to_mut_any_dataset_vec()
             .into_iter()
@@ -349,8 +340,143 @@ impl AllDatasets {
             &path_to_last,
         )?;
 
+        MapDependencies::export()?;
+
         Ok(())
     }
+
+    /// Logs every problem found in the exported datasets and returns how many there were
+    pub fn verify(&self) -> usize {
+        let inserted = self.min_initial_states.inserted;
+
+        let date_metadata_last_date = self.date_metadata.last_height.get_initial_last_date();
+        let date_metadata_last_height =
+            date_metadata_last_date.and_then(|date| self.date_metadata.last_height.get(&date));
+
+        // Like the first days of 2009
+        let dates_without_blocks = {
+            let first_heights = self.date_metadata.first_height.import_all_entries();
+
+            match (first_heights.keys().next(), first_heights.keys().last()) {
+                (Some(first), Some(last)) => first
+                    .iter_up_to(last)
+                    .filter(|date| !first_heights.contains_key(date))
+                    .collect::<BTreeSet<_>>(),
+                _ => BTreeSet::default(),
+            }
+        };
+
+        let mut issues = self
+            .to_any_dataset_vec()
+            .into_par_iter()
+            .flat_map(|dataset| {
+                let mut issues = dataset
+                    .to_all_map_vec()
+                    .into_iter()
+                    .flat_map(|map| {
+                        map.verify(&dates_without_blocks)
+                            .into_iter()
+                            .map(|issue| (map.path().to_owned(), issue))
+                    })
+                    .collect_vec();
+
+                dataset
+                    .to_all_computed_height_map_vec()
+                    .into_iter()
+                    .for_each(|map| {
+                        let last_height = map.get_initial_last_height();
+
+                        if inserted
+                            .last_height
+                            .is_some_and(|inserted| last_height < Some(inserted))
+                        {
+                            issues.push((
+                                map.path().to_owned(),
+                                format!(
+                                    "Computed up to {last_height:?} while inserted datasets go up to {:?}",
+                                    inserted.last_height
+                                ),
+                            ));
+                        }
+                    });
+
+                dataset
+                    .to_all_computed_date_map_vec()
+                    .into_iter()
+                    .for_each(|map| {
+                        let last_date = map.get_initial_last_date();
+
+                        if inserted
+                            .last_date
+                            .is_some_and(|inserted| last_date < Some(inserted))
+                        {
+                            issues.push((
+                                map.path().to_owned(),
+                                format!(
+                                    "Computed up to {last_date:?} while inserted datasets go up to {:?}",
+                                    inserted.last_date
+                                ),
+                            ));
+                        }
+                    });
+
+                [
+                    dataset.to_all_inserted_date_map_vec(),
+                    dataset.to_all_computed_date_map_vec(),
+                ]
+                .concat()
+                .into_iter()
+                .for_each(|map| {
+                    if let (Some(last_date), Some(date_metadata_last_date)) =
+                        (map.get_initial_last_date(), date_metadata_last_date)
+                    {
+                        if last_date > date_metadata_last_date {
+                            issues.push((
+                                map.path().to_owned(),
+                                format!("Goes up to {last_date} while date_metadata stops at {date_metadata_last_date}"),
+                            ));
+                        }
+                    }
+                });
+
+                [
+                    dataset.to_all_inserted_height_map_vec(),
+                    dataset.to_all_computed_height_map_vec(),
+                ]
+                .concat()
+                .into_iter()
+                .for_each(|map| {
+                    if let (Some(last_height), Some(date_metadata_last_height)) =
+                        (map.get_initial_last_height(), date_metadata_last_height)
+                    {
+                        if last_height > date_metadata_last_height {
+                            issues.push((
+                                map.path().to_owned(),
+                                format!("Goes up to {last_height} while date_metadata stops at {date_metadata_last_height}"),
+                            ));
+                        }
+                    }
+                });
+
+                issues
+            })
+            .collect::<Vec<_>>();
+
+        issues.extend(
+            self.date_metadata
+                .verify()
+                .into_iter()
+                .map(|issue| (self.date_metadata.first_height.path().to_owned(), issue)),
+        );
+
+        issues.sort_unstable();
+
+        issues.iter().for_each(|(path, issue)| {
+            log(&format!("{}: {issue}", path.display()));
+        });
+
+        issues.len()
+    }
 }
 
 impl AnyDatasets for AllDatasets {
@@ -373,6 +499,9 @@ impl AnyDatasets for AllDatasets {
                 &self.date_metadata,
                 &self.cointime,
                 &self.coindays,
+                &self.dormant_supply,
+                &self.indicators,
+                &self.backtest,
             ],
         ]
         .into_iter()
@@ -392,6 +521,9 @@ impl AnyDatasets for AllDatasets {
                 &mut self.date_metadata,
                 &mut self.cointime,
                 &mut self.coindays,
+                &mut self.dormant_supply,
+                &mut self.indicators,
+                &mut self.backtest,
             ],
         ]
         .into_iter()
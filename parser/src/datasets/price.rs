@@ -9,10 +9,10 @@ use struct_iterable::Iterable;
 use crate::{
     price::{Binance, Kibo, Kraken},
     structs::{
-        Amount, BiMap, Config, Date, DateMap, DateMapChunkId, Height, HeightMapChunkId, MapKey,
-        MapKind, Timestamp, OHLC,
+        Amount, BiMap, Config, Date, DateMap, DateMapChunkId, Height, HeightMap, HeightMapChunkId,
+        MapKey, MapKind, Timestamp, OHLC,
     },
-    utils::{ONE_MONTH_IN_DAYS, ONE_WEEK_IN_DAYS, ONE_YEAR_IN_DAYS},
+    utils::{ONE_MONTH_IN_DAYS, ONE_WEEK_IN_DAYS, ONE_YEAR_IN_DAYS, TARGET_BLOCKS_PER_DAY},
 };
 
 use super::{AnyDataset, ComputeData, MinInitialStates, RatioDataset};
@@ -59,6 +59,14 @@ pub struct PriceDatasets {
     pub price_89d_sma_ratio: RatioDataset,
     pub price_144d_sma: BiMap<f32>,
     pub price_144d_sma_ratio: RatioDataset,
+    pub price_111d_sma: BiMap<f32>,
+    pub price_200d_sma: BiMap<f32>,
+    /// Ratio is the Mayer multiple
+    pub price_200d_sma_ratio: RatioDataset,
+    pub price_350d_sma: BiMap<f32>,
+    pub price_350d_sma_x2: BiMap<f32>,
+    /// Tops when crossing 1
+    pub pi_cycle_ratio: BiMap<f32>,
     pub price_200w_sma: BiMap<f32>,
     pub price_200w_sma_ratio: RatioDataset,
     pub price_1d_total_return: DateMap<f32>,
@@ -76,12 +84,39 @@ pub struct PriceDatasets {
     pub all_time_high: BiMap<f32>,
     pub all_time_high_date: DateMap<Date>,
     pub days_since_all_time_high: DateMap<u32>,
+    /// Max drawdown duration
     pub max_days_between_all_time_highs: DateMap<u32>,
     pub max_years_between_all_time_highs: DateMap<f32>,
     pub market_price_to_all_time_high_ratio: BiMap<f32>,
     pub drawdown: BiMap<f32>,
+    /// Date of the lowest low since the last all time high
+    pub drawdown_low_date: DateMap<Date>,
+    /// Days it took to go from the lowest low of the last finished drawdown to a new all time high
+    pub last_recovery_time: DateMap<u32>,
+    pub max_recovery_time: DateMap<u32>,
     pub sats_per_dollar: BiMap<f32>,
-    // volatility
+    pub price_1d_log_return: DateMap<f32>,
+    pub price_1d_log_return_1w_standard_deviation: DateMap<f32>,
+    pub price_1d_log_return_1m_standard_deviation: DateMap<f32>,
+    pub price_1d_log_return_1y_standard_deviation: DateMap<f32>,
+    /// Annualized, from the daily closes
+    pub price_1w_realized_volatility: DateMap<f32>,
+    pub price_1m_realized_volatility: DateMap<f32>,
+    pub price_1y_realized_volatility: DateMap<f32>,
+    /// Parkinson estimator, from the high and low of each block
+    pub price_intraday_variance: HeightMap<f64>,
+    pub price_intraday_variance_1w_sum: HeightMap<f64>,
+    pub price_intraday_variance_1m_sum: HeightMap<f64>,
+    pub price_intraday_variance_1y_sum: HeightMap<f64>,
+    /// Annualized, from the blocks' OHLC
+    pub price_1w_intraday_realized_volatility: BiMap<f32>,
+    pub price_1m_intraday_realized_volatility: BiMap<f32>,
+    pub price_1y_intraday_realized_volatility: BiMap<f32>,
+    pub price_1d_downside_log_return_squared: DateMap<f32>,
+    pub price_1d_downside_log_return_squared_1y_sum: DateMap<f64>,
+    /// Without a risk free rate
+    pub price_1y_sharpe_ratio: DateMap<f32>,
+    pub price_1y_sortino_ratio: DateMap<f32>,
 }
 
 impl PriceDatasets {
@@ -138,6 +173,12 @@ impl PriceDatasets {
             price_89d_sma_ratio: RatioDataset::import(datasets_path, "price_89d_sma", config)?,
             price_144d_sma: BiMap::new_bin(1, MapKind::Computed, &f("price_144d_sma")),
             price_144d_sma_ratio: RatioDataset::import(datasets_path, "price_144d_sma", config)?,
+            price_111d_sma: BiMap::new_bin(1, MapKind::Computed, &f("price_111d_sma")),
+            price_200d_sma: BiMap::new_bin(1, MapKind::Computed, &f("price_200d_sma")),
+            price_200d_sma_ratio: RatioDataset::import(datasets_path, "price_200d_sma", config)?,
+            price_350d_sma: BiMap::new_bin(1, MapKind::Computed, &f("price_350d_sma")),
+            price_350d_sma_x2: BiMap::new_bin(1, MapKind::Computed, &f("price_350d_sma_x2")),
+            pi_cycle_ratio: BiMap::new_bin(1, MapKind::Computed, &f("pi_cycle_ratio")),
             price_200w_sma: BiMap::new_bin(1, MapKind::Computed, &f("price_200w_sma")),
             price_200w_sma_ratio: RatioDataset::import(datasets_path, "price_200w_sma", config)?,
             price_1d_total_return: DateMap::new_bin(
@@ -218,7 +259,96 @@ impl PriceDatasets {
                 &f("market_price_to_all_time_high_ratio"),
             ),
             drawdown: BiMap::new_bin(1, MapKind::Computed, &f("drawdown")),
+            drawdown_low_date: DateMap::new_bin(1, MapKind::Computed, &f("drawdown_low_date")),
+            last_recovery_time: DateMap::new_bin(1, MapKind::Computed, &f("last_recovery_time")),
+            max_recovery_time: DateMap::new_bin(1, MapKind::Computed, &f("max_recovery_time")),
             sats_per_dollar: BiMap::new_bin(1, MapKind::Computed, &f("sats_per_dollar")),
+            price_1d_log_return: DateMap::new_bin(1, MapKind::Computed, &f("price_1d_log_return")),
+            price_1d_log_return_1w_standard_deviation: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1d_log_return_1w_standard_deviation"),
+            ),
+            price_1d_log_return_1m_standard_deviation: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1d_log_return_1m_standard_deviation"),
+            ),
+            price_1d_log_return_1y_standard_deviation: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1d_log_return_1y_standard_deviation"),
+            ),
+            price_1w_realized_volatility: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1w_realized_volatility"),
+            ),
+            price_1m_realized_volatility: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1m_realized_volatility"),
+            ),
+            price_1y_realized_volatility: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1y_realized_volatility"),
+            ),
+            price_intraday_variance: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_intraday_variance"),
+            ),
+            price_intraday_variance_1w_sum: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_intraday_variance_1w_sum"),
+            ),
+            price_intraday_variance_1m_sum: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_intraday_variance_1m_sum"),
+            ),
+            price_intraday_variance_1y_sum: HeightMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_intraday_variance_1y_sum"),
+            ),
+            price_1w_intraday_realized_volatility: BiMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1w_intraday_realized_volatility"),
+            ),
+            price_1m_intraday_realized_volatility: BiMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1m_intraday_realized_volatility"),
+            ),
+            price_1y_intraday_realized_volatility: BiMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1y_intraday_realized_volatility"),
+            ),
+            price_1d_downside_log_return_squared: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1d_downside_log_return_squared"),
+            ),
+            price_1d_downside_log_return_squared_1y_sum: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1d_downside_log_return_squared_1y_sum"),
+            ),
+            price_1y_sharpe_ratio: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1y_sharpe_ratio"),
+            ),
+            price_1y_sortino_ratio: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("price_1y_sortino_ratio"),
+            ),
         };
 
         s.min_initial_states
@@ -227,7 +357,12 @@ impl PriceDatasets {
         Ok(s)
     }
 
-    pub fn compute(&mut self, compute_data: &ComputeData, circulating_supply: &mut BiMap<f64>) {
+    pub fn compute(
+        &mut self,
+        compute_data: &ComputeData,
+        circulating_supply: &mut BiMap<f64>,
+        last_height: &mut DateMap<Height>,
+    ) {
         let &ComputeData { dates, heights, .. } = compute_data;
 
         self.open
@@ -301,6 +436,29 @@ impl PriceDatasets {
         self.price_144d_sma
             .multi_insert_simple_average(heights, dates, &mut self.close, 144);
 
+        self.price_111d_sma
+            .multi_insert_simple_average(heights, dates, &mut self.close, 111);
+
+        self.price_200d_sma
+            .multi_insert_simple_average(heights, dates, &mut self.close, 200);
+
+        self.price_350d_sma
+            .multi_insert_simple_average(heights, dates, &mut self.close, 350);
+
+        self.price_350d_sma_x2.multi_insert_simple_transform(
+            heights,
+            dates,
+            &mut self.price_350d_sma,
+            &|v| v * 2.0,
+        );
+
+        self.pi_cycle_ratio.multi_insert_divide(
+            heights,
+            dates,
+            &mut self.price_111d_sma,
+            &mut self.price_350d_sma_x2,
+        );
+
         self.price_200w_sma.multi_insert_simple_average(
             heights,
             dates,
@@ -394,6 +552,8 @@ impl PriceDatasets {
             .compute(compute_data, &mut self.close, &mut self.price_89d_sma);
         self.price_144d_sma_ratio
             .compute(compute_data, &mut self.close, &mut self.price_144d_sma);
+        self.price_200d_sma_ratio
+            .compute(compute_data, &mut self.close, &mut self.price_200d_sma);
         self.price_200w_sma_ratio
             .compute(compute_data, &mut self.close, &mut self.price_200w_sma);
 
@@ -442,12 +602,223 @@ impl PriceDatasets {
             &|v| -(100.0 - v),
         );
 
+        self.drawdown_low_date.multi_insert_complex_transform(
+            dates,
+            &mut self.all_time_high_date,
+            |(all_time_high_date, date, _, map)| {
+                if all_time_high_date == *date {
+                    return *date;
+                }
+
+                let previous_date = date.checked_sub(1).unwrap();
+                let previous_low_date = map.get_or_import(&previous_date).unwrap_or(*date);
+
+                let low = self.low.date.get_or_import(date).unwrap();
+                let previous_low = self.low.date.get_or_import(&previous_low_date).unwrap();
+
+                if low < previous_low {
+                    *date
+                } else {
+                    previous_low_date
+                }
+            },
+        );
+
+        self.last_recovery_time.multi_insert_complex_transform(
+            dates,
+            &mut self.all_time_high_date,
+            |(all_time_high_date, date, all_time_high_dates, map)| {
+                let previous_date = date.checked_sub(1).unwrap();
+
+                let was_in_drawdown = all_time_high_dates
+                    .get_or_import(&previous_date)
+                    .is_some_and(|previous_all_time_high_date| {
+                        previous_all_time_high_date != previous_date
+                    });
+
+                if all_time_high_date == *date && was_in_drawdown {
+                    let low_date = self
+                        .drawdown_low_date
+                        .get_or_import(&previous_date)
+                        .unwrap();
+
+                    date.difference_in_days_between(low_date)
+                } else {
+                    map.get_or_import(&previous_date).unwrap_or_default()
+                }
+            },
+        );
+
+        self.max_recovery_time
+            .multi_insert_max(dates, &mut self.last_recovery_time);
+
         self.sats_per_dollar.multi_insert_simple_transform(
             heights,
             dates,
             &mut self.close,
             &|price| Amount::ONE_BTC_F32 / price,
         );
+
+        self.compute_volatility(compute_data, last_height);
+    }
+
+    fn compute_volatility(
+        &mut self,
+        &ComputeData { heights, dates, .. }: &ComputeData,
+        last_height: &mut DateMap<Height>,
+    ) {
+        let annualize = |standard_deviation: f32, _: &Date| {
+            standard_deviation * (ONE_YEAR_IN_DAYS as f32).sqrt() * 100.0
+        };
+
+        self.price_1d_log_return.multi_insert_complex_transform(
+            dates,
+            &mut self.close.date,
+            |(close, date, closes, _)| Self::log_return(closes, close, date, 1).unwrap_or_default(),
+        );
+
+        [
+            (
+                &mut self.price_1d_log_return_1w_standard_deviation,
+                &mut self.price_1w_realized_volatility,
+                ONE_WEEK_IN_DAYS,
+            ),
+            (
+                &mut self.price_1d_log_return_1m_standard_deviation,
+                &mut self.price_1m_realized_volatility,
+                ONE_MONTH_IN_DAYS,
+            ),
+            (
+                &mut self.price_1d_log_return_1y_standard_deviation,
+                &mut self.price_1y_realized_volatility,
+                ONE_YEAR_IN_DAYS,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(standard_deviation, volatility, days)| {
+            standard_deviation.multi_insert_standard_deviation(
+                dates,
+                &mut self.price_1d_log_return,
+                Some(days),
+            );
+
+            volatility.multi_insert_simple_transform(dates, standard_deviation, annualize);
+        });
+
+        self.price_intraday_variance.multi_insert_simple_transform(
+            heights,
+            &mut self.ohlc.height,
+            |ohlc, _| {
+                let variance =
+                    (ohlc.high as f64 / ohlc.low as f64).ln().powi(2) / (4.0 * 2.0_f64.ln());
+
+                if variance.is_finite() {
+                    variance
+                } else {
+                    0.0
+                }
+            },
+        );
+
+        [
+            (
+                &mut self.price_intraday_variance_1w_sum,
+                &mut self.price_1w_intraday_realized_volatility,
+                ONE_WEEK_IN_DAYS,
+            ),
+            (
+                &mut self.price_intraday_variance_1m_sum,
+                &mut self.price_1m_intraday_realized_volatility,
+                ONE_MONTH_IN_DAYS,
+            ),
+            (
+                &mut self.price_intraday_variance_1y_sum,
+                &mut self.price_1y_intraday_realized_volatility,
+                ONE_YEAR_IN_DAYS,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(variance_sum, volatility, days)| {
+            variance_sum.multi_insert_last_x_sum(
+                heights,
+                &mut self.price_intraday_variance,
+                TARGET_BLOCKS_PER_DAY * days,
+            );
+
+            volatility.height.multi_insert_simple_transform(
+                heights,
+                variance_sum,
+                |variance_sum, _| {
+                    ((variance_sum * ONE_YEAR_IN_DAYS as f64 / days as f64).sqrt() * 100.0) as f32
+                },
+            );
+
+            volatility.date.multi_insert_complex_transform(
+                dates,
+                last_height,
+                |(last_height, ..)| volatility.height.get_or_import(&last_height).unwrap(),
+            );
+        });
+
+        self.price_1d_downside_log_return_squared
+            .multi_insert_simple_transform(
+                dates,
+                &mut self.price_1d_log_return,
+                |log_return, _| log_return.min(0.0).powi(2),
+            );
+
+        self.price_1d_downside_log_return_squared_1y_sum
+            .multi_insert_last_x_sum(
+                dates,
+                &mut self.price_1d_downside_log_return_squared,
+                ONE_YEAR_IN_DAYS,
+            );
+
+        self.price_1y_sharpe_ratio.multi_insert_complex_transform(
+            dates,
+            &mut self.price_1d_log_return_1y_standard_deviation,
+            |(standard_deviation, date, ..)| {
+                let close = self.close.date.get_or_import(date).unwrap();
+
+                let ratio = Self::log_return(&mut self.close.date, close, date, ONE_YEAR_IN_DAYS)
+                    .unwrap_or_default()
+                    / (standard_deviation * (ONE_YEAR_IN_DAYS as f32).sqrt());
+
+                if ratio.is_finite() {
+                    ratio
+                } else {
+                    0.0
+                }
+            },
+        );
+
+        self.price_1y_sortino_ratio.multi_insert_complex_transform(
+            dates,
+            &mut self.price_1d_downside_log_return_squared_1y_sum,
+            |(downside_sum, date, ..)| {
+                let close = self.close.date.get_or_import(date).unwrap();
+
+                // Annualized downside deviation is sqrt(sum / 365) * sqrt(365)
+                let ratio = Self::log_return(&mut self.close.date, close, date, ONE_YEAR_IN_DAYS)
+                    .unwrap_or_default()
+                    / downside_sum.sqrt() as f32;
+
+                if ratio.is_finite() {
+                    ratio
+                } else {
+                    0.0
+                }
+            },
+        );
+    }
+
+    /// Natural log of the change of the close over the last `days`
+    fn log_return(closes: &mut DateMap<f32>, close: f32, date: &Date, days: usize) -> Option<f32> {
+        let previous_close = closes.get_or_import(&date.checked_sub(days)?)?;
+
+        let log_return = (close / previous_close).ln();
+
+        log_return.is_finite().then_some(log_return)
     }
 
     pub fn get_date_ohlc(&mut self, date: Date) -> color_eyre::Result<OHLC> {
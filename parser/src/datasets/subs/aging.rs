@@ -0,0 +1,84 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::{
+    datasets::{AnyDataset, InsertData, MinInitialStates},
+    states::OutputState,
+    structs::{BiMap, Config, MapKind},
+    DateMap, HeightMap,
+};
+
+/// Supply crossing into an age cohort, the only way the cohorts that can't receive grow
+#[derive(Allocative, Iterable)]
+pub struct AgingSubDataset {
+    min_initial_states: MinInitialStates,
+
+    // Inserted
+    pub count: BiMap<u64>,
+    pub volume: HeightMap<f64>,
+    pub volume_1d_sum: DateMap<f64>,
+}
+
+impl AgingSubDataset {
+    pub fn import(
+        parent_path: &str,
+        name: &Option<String>,
+        config: &Config,
+    ) -> color_eyre::Result<Self> {
+        let f = |s: &str| {
+            if let Some(name) = name {
+                format!("{parent_path}/{name}/{s}")
+            } else {
+                format!("{parent_path}/{s}")
+            }
+        };
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // ---
+            // Inserted
+            // ---
+            count: BiMap::new_bin(1, MapKind::Inserted, &f("aging_inflow_count")),
+            volume: HeightMap::new_bin(1, MapKind::Inserted, &f("aging_inflow_volume")),
+            volume_1d_sum: DateMap::new_bin(1, MapKind::Inserted, &f("aging_inflow_volume_1d_sum")),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    pub fn insert(
+        &mut self,
+        &InsertData {
+            height,
+            date,
+            is_date_last_block,
+            date_blocks_range,
+            ..
+        }: &InsertData,
+        state: &OutputState,
+    ) {
+        let count = self
+            .count
+            .height
+            .insert(height, state.count().round() as u64);
+
+        self.volume.insert(height, state.volume().to_btc());
+
+        if is_date_last_block {
+            self.count.date.insert(date, count);
+
+            self.volume_1d_sum
+                .insert(date, self.volume.sum_range(date_blocks_range));
+        }
+    }
+}
+
+impl AnyDataset for AgingSubDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
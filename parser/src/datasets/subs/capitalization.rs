@@ -17,6 +17,8 @@ pub struct CapitalizationDataset {
     pub realized_cap: BiMap<f32>,
     pub realized_price: BiMap<f32>,
     realized_cap_1m_net_change: BiMap<f32>,
+    /// Realized HODL waves when looking at the age cohorts
+    realized_cap_to_total_realized_cap_ratio: BiMap<f32>,
     realized_price_ratio: RatioDataset,
 }
 
@@ -51,6 +53,11 @@ impl CapitalizationDataset {
                 &f("realized_cap_1m_net_change"),
             ),
             realized_price: BiMap::new_bin(1, MapKind::Computed, &f("realized_price")),
+            realized_cap_to_total_realized_cap_ratio: BiMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("realized_cap_to_total_realized_cap_ratio"),
+            ),
             realized_price_ratio: RatioDataset::import(
                 parent_path,
                 &format!(
@@ -92,6 +99,7 @@ impl CapitalizationDataset {
         compute_data: &ComputeData,
         closes: &mut BiMap<f32>,
         cohort_supply: &mut BiMap<f64>,
+        total_realized_cap: &mut BiMap<f32>,
     ) {
         let &ComputeData { heights, dates, .. } = compute_data;
 
@@ -109,6 +117,9 @@ impl CapitalizationDataset {
             ONE_MONTH_IN_DAYS,
         );
 
+        self.realized_cap_to_total_realized_cap_ratio
+            .multi_insert_percentage(heights, dates, &mut self.realized_cap, total_realized_cap);
+
         self.realized_price_ratio
             .compute(compute_data, closes, &mut self.realized_price);
     }
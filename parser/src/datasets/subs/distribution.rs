@@ -0,0 +1,75 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::{
+    datasets::{AnyDataset, InsertData, MinInitialStates},
+    states::PriceToValue,
+    structs::{Amount, Config, DateMap, Distribution, MapKind, Price},
+};
+
+#[derive(Allocative, Iterable)]
+pub struct DistributionSubDataset {
+    min_initial_states: MinInitialStates,
+
+    pub urpd: DateMap<Distribution>,
+}
+
+impl DistributionSubDataset {
+    pub fn import(
+        parent_path: &str,
+        name: &Option<String>,
+        config: &Config,
+    ) -> color_eyre::Result<Self> {
+        let f = |s: &str| {
+            if let Some(name) = name {
+                format!("{parent_path}/{name}/{s}")
+            } else {
+                format!("{parent_path}/{s}")
+            }
+        };
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // ---
+            // Inserted
+            // ---
+            // Too big to be in the last values
+            urpd: DateMap::_new_bin(1, MapKind::Inserted, &f("urpd"), false),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    /// Snapshot of the whole cost basis, once a day
+    pub fn insert(
+        &mut self,
+        &InsertData {
+            is_date_last_block,
+            date,
+            ..
+        }: &InsertData,
+        price_to_amount: &PriceToValue<Amount>,
+    ) {
+        if !is_date_last_block {
+            return;
+        }
+
+        let mut distribution = Distribution::default();
+
+        price_to_amount.iter().for_each(|(cent, amount)| {
+            distribution.increment(Price::from_cent(*cent as u64), *amount);
+        });
+
+        self.urpd.insert(date, distribution);
+    }
+}
+
+impl AnyDataset for DistributionSubDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
@@ -0,0 +1,117 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
+use crate::{
+    datasets::{AnyDataset, ComputeData, InsertData, MinInitialStates},
+    structs::{Amount, BiMap, Config, MapKind},
+    DateMap, HeightMap,
+};
+
+#[derive(Allocative, Iterable)]
+pub struct LivelinessSubDataset {
+    min_initial_states: MinInitialStates,
+
+    // Inserted
+    pub coindays_destroyed: HeightMap<f32>,
+    pub coindays_destroyed_1d_sum: DateMap<f32>,
+
+    // Computed
+    pub cumulative_coindays_destroyed: DateMap<f32>,
+    pub cumulative_coindays_created: DateMap<f32>,
+    pub liveliness: DateMap<f32>,
+}
+
+impl LivelinessSubDataset {
+    pub fn import(
+        parent_path: &str,
+        name: &Option<String>,
+        config: &Config,
+    ) -> color_eyre::Result<Self> {
+        let f = |s: &str| {
+            if let Some(name) = name {
+                format!("{parent_path}/{name}/{s}")
+            } else {
+                format!("{parent_path}/{s}")
+            }
+        };
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // ---
+            // Inserted
+            // ---
+            coindays_destroyed: HeightMap::new_bin(1, MapKind::Inserted, &f("coindays_destroyed")),
+            coindays_destroyed_1d_sum: DateMap::new_bin(
+                1,
+                MapKind::Inserted,
+                &f("coindays_destroyed_1d_sum"),
+            ),
+
+            // ---
+            // Computed
+            // ---
+            cumulative_coindays_destroyed: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("cumulative_coindays_destroyed"),
+            ),
+            cumulative_coindays_created: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("cumulative_coindays_created"),
+            ),
+            liveliness: DateMap::new_bin(1, MapKind::Computed, &f("liveliness")),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    pub fn insert(
+        &mut self,
+        &InsertData {
+            height,
+            date,
+            is_date_last_block,
+            date_blocks_range,
+            ..
+        }: &InsertData,
+        satdays_destroyed: Amount,
+    ) {
+        self.coindays_destroyed
+            .insert(height, satdays_destroyed.to_btc() as f32);
+
+        if is_date_last_block {
+            self.coindays_destroyed_1d_sum
+                .insert(date, self.coindays_destroyed.sum_range(date_blocks_range));
+        }
+    }
+
+    pub fn compute(
+        &mut self,
+        &ComputeData { dates, .. }: &ComputeData,
+        cohort_supply: &mut BiMap<f64>,
+    ) {
+        self.cumulative_coindays_destroyed
+            .multi_insert_cumulative(dates, &mut self.coindays_destroyed_1d_sum);
+
+        // Every coin of the cohort stores one coinday per day
+        self.cumulative_coindays_created
+            .multi_insert_cumulative(dates, &mut cohort_supply.date);
+
+        self.liveliness.multi_insert_divide(
+            dates,
+            &mut self.cumulative_coindays_destroyed,
+            &mut self.cumulative_coindays_created,
+        );
+    }
+}
+
+impl AnyDataset for LivelinessSubDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
@@ -1,21 +1,31 @@
 use allocative::Allocative;
 
+mod aging;
 mod capitalization;
+mod distribution;
 mod input;
+mod liveliness;
+mod output;
 mod price_paid;
 mod ratio;
 mod realized;
 mod recap;
+mod strategy;
 mod supply;
 mod unrealized;
 mod utxo;
 
+pub use aging::*;
 pub use capitalization::*;
+pub use distribution::*;
 pub use input::*;
+pub use liveliness::*;
+pub use output::*;
 pub use price_paid::*;
 pub use ratio::*;
 pub use realized::*;
 pub use recap::*;
+pub use strategy::*;
 use struct_iterable::Iterable;
 pub use supply::*;
 pub use unrealized::*;
@@ -27,9 +37,12 @@ use super::AnyDatasetGroup;
 
 #[derive(Allocative, Iterable)]
 pub struct SubDataset {
+    /// `None` for the cohorts that can receive, the others only grow by aging
+    pub aging: Option<AgingSubDataset>,
     pub capitalization: CapitalizationDataset,
     pub input: InputSubDataset,
-    // pub output: OutputSubDataset,
+    /// `None` for the UTXO cohorts that can't receive, see `UTXOCohortId::can_receive`
+    pub output: Option<OutputSubDataset>,
     pub price_paid: PricePaidSubDataset,
     pub realized: RealizedSubDataset,
     pub supply: SupplySubDataset,
@@ -41,12 +54,18 @@ impl SubDataset {
     pub fn import(
         parent_path: &str,
         name: &Option<String>,
+        has_output: bool,
         config: &Config,
     ) -> color_eyre::Result<Self> {
         let s = Self {
+            aging: (!has_output)
+                .then(|| AgingSubDataset::import(parent_path, name, config))
+                .transpose()?,
             capitalization: CapitalizationDataset::import(parent_path, name, config)?,
             input: InputSubDataset::import(parent_path, name, config)?,
-            // output: OutputSubDataset::import(parent_path)?,
+            output: has_output
+                .then(|| OutputSubDataset::import(parent_path, name, config))
+                .transpose()?,
             price_paid: PricePaidSubDataset::import(parent_path, name, config)?,
             realized: RealizedSubDataset::import(parent_path, name, config)?,
             supply: SupplySubDataset::import(parent_path, name, config)?,
@@ -60,7 +79,7 @@ impl SubDataset {
 
 impl AnyDatasetGroup for SubDataset {
     fn as_vec(&self) -> Vec<&(dyn AnyDataset + Send + Sync)> {
-        vec![
+        let mut v: Vec<&(dyn AnyDataset + Send + Sync)> = vec![
             &self.capitalization,
             &self.price_paid,
             &self.realized,
@@ -68,12 +87,21 @@ impl AnyDatasetGroup for SubDataset {
             &self.unrealized,
             &self.utxo,
             &self.input,
-            // &self.output,
-        ]
+        ];
+
+        if let Some(output) = self.output.as_ref() {
+            v.push(output);
+        }
+
+        if let Some(aging) = self.aging.as_ref() {
+            v.push(aging);
+        }
+
+        v
     }
 
     fn as_mut_vec(&mut self) -> Vec<&mut dyn AnyDataset> {
-        vec![
+        let mut v: Vec<&mut dyn AnyDataset> = vec![
             &mut self.capitalization,
             &mut self.price_paid,
             &mut self.realized,
@@ -81,7 +109,16 @@ impl AnyDatasetGroup for SubDataset {
             &mut self.unrealized,
             &mut self.utxo,
             &mut self.input,
-            // &mut self.output,
-        ]
+        ];
+
+        if let Some(output) = self.output.as_mut() {
+            v.push(output);
+        }
+
+        if let Some(aging) = self.aging.as_mut() {
+            v.push(aging);
+        }
+
+        v
     }
 }
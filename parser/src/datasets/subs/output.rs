@@ -1,38 +1,66 @@
+use allocative::Allocative;
+use struct_iterable::Iterable;
+
 use crate::{
     datasets::{AnyDataset, ComputeData, InsertData, MinInitialStates},
     states::OutputState,
-    structs::{AnyBiMap, BiMap},
+    structs::{BiMap, Config, MapKind},
     utils::ONE_YEAR_IN_DAYS,
+    DateMap, HeightMap,
 };
 
+#[derive(Allocative, Iterable)]
 pub struct OutputSubDataset {
     min_initial_states: MinInitialStates,
 
     // Inserted
-    pub count: BiMap<f32>,
-    pub volume: BiMap<f32>,
+    pub count: BiMap<u64>,
+    pub volume: HeightMap<f64>,
+    pub volume_1d_sum: DateMap<f64>,
 
     // Computed
-    pub annualized_volume: BiMap<f32>,
-    pub velocity: BiMap<f32>,
+    pub annualized_volume: DateMap<f64>,
+    pub velocity: DateMap<f64>,
     // add outputs_per_second
 }
 
 impl OutputSubDataset {
-    pub fn import(parent_path: &str) -> color_eyre::Result<Self> {
-        let f = |s: &str| format!("{parent_path}/{s}");
+    pub fn import(
+        parent_path: &str,
+        name: &Option<String>,
+        config: &Config,
+    ) -> color_eyre::Result<Self> {
+        let f = |s: &str| {
+            if let Some(name) = name {
+                format!("{parent_path}/{name}/{s}")
+            } else {
+                format!("{parent_path}/{s}")
+            }
+        };
 
         let mut s = Self {
             min_initial_states: MinInitialStates::default(),
 
-            count: BiMap::new_bin(1, &f("output_count")),
-            volume: BiMap::new_bin(1, &f("output_volume")),
-            annualized_volume: BiMap::new_bin(1, &f("annualized_output_volume")),
-            velocity: BiMap::new_bin(1, &f("output_velocity")),
+            // ---
+            // Inserted
+            // ---
+            count: BiMap::new_bin(1, MapKind::Inserted, &f("output_count")),
+            volume: HeightMap::new_bin(1, MapKind::Inserted, &f("output_volume")),
+            volume_1d_sum: DateMap::new_bin(1, MapKind::Inserted, &f("output_volume_1d_sum")),
+
+            // ---
+            // Computed
+            // ---
+            annualized_volume: DateMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("annualized_output_volume"),
+            ),
+            velocity: DateMap::new_bin(1, MapKind::Computed, &f("output_velocity")),
         };
 
         s.min_initial_states
-            .consume(MinInitialStates::compute_from_dataset(&s));
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
 
         Ok(s)
     }
@@ -48,34 +76,36 @@ impl OutputSubDataset {
         }: &InsertData,
         state: &OutputState,
     ) {
-        let count = self.count.height.insert(height, state.count);
+        let count = self
+            .count
+            .height
+            .insert(height, state.count().round() as u64);
 
-        self.volume.height.insert(height, state.volume);
+        self.volume.insert(height, state.volume().to_btc());
 
         if is_date_last_block {
             self.count.date.insert(date, count);
 
-            self.volume.date_insert_sum_range(date, date_blocks_range);
+            self.volume_1d_sum
+                .insert(date, self.volume.sum_range(date_blocks_range));
         }
     }
 
     pub fn compute(
         &mut self,
-        &ComputeData { heights, dates }: &ComputeData,
-        cohort_supply: &mut BiMap<f32>,
+        &ComputeData { dates, .. }: &ComputeData,
+        cohort_supply: &mut BiMap<f64>,
     ) {
         self.annualized_volume.multi_insert_last_x_sum(
-            heights,
             dates,
-            &mut self.volume,
+            &mut self.volume_1d_sum,
             ONE_YEAR_IN_DAYS,
         );
 
         self.velocity.multi_insert_divide(
-            heights,
             dates,
             &mut self.annualized_volume,
-            cohort_supply,
+            &mut cohort_supply.date,
         );
     }
 }
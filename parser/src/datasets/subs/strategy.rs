@@ -0,0 +1,171 @@
+use allocative::Allocative;
+use chrono::{Datelike, Weekday};
+use struct_iterable::Iterable;
+
+use crate::{
+    datasets::{AnyDataset, ComputeData, MinInitialStates},
+    structs::{Amount, Config, Date, DateMap, MapKind},
+    utils::ONE_YEAR_IN_DAYS,
+};
+
+/// Days on which a strategy is allowed to buy
+#[derive(Debug, Clone, Copy)]
+pub enum Schedule {
+    Daily,
+    /// Every monday
+    Weekly,
+    /// Every first day of the month
+    Monthly,
+}
+
+impl Schedule {
+    pub fn is_trigger_date(&self, date: &Date) -> bool {
+        match self {
+            Self::Daily => true,
+            Self::Weekly => date.weekday() == Weekday::Mon,
+            Self::Monthly => date.day() == 1,
+        }
+    }
+}
+
+///
+/// Replays a strategy buying one dollar worth of bitcoin at the close of every day allowed by its rule.
+///
+/// The results are those of the strategy started 1 and 4 years before each date.
+///
+#[derive(Allocative, Iterable)]
+pub struct StrategySubDataset {
+    min_initial_states: MinInitialStates,
+
+    // Computed
+    pub invested: DateMap<f64>,
+    pub bought: DateMap<f64>,
+    pub invested_1y: DateMap<f64>,
+    pub stack_1y: DateMap<f64>,
+    pub cost_basis_1y: DateMap<f32>,
+    pub return_1y: DateMap<f32>,
+    pub invested_4y: DateMap<f64>,
+    pub stack_4y: DateMap<f64>,
+    pub cost_basis_4y: DateMap<f32>,
+    pub return_4y: DateMap<f32>,
+}
+
+impl StrategySubDataset {
+    pub fn import(parent_path: &str, name: &str, config: &Config) -> color_eyre::Result<Self> {
+        let f = |s: &str| format!("{parent_path}/{name}_{s}");
+
+        let mut s = Self {
+            min_initial_states: MinInitialStates::default(),
+
+            // ---
+            // Computed
+            // ---
+            invested: DateMap::new_bin(1, MapKind::Computed, &f("invested")),
+            bought: DateMap::new_bin(1, MapKind::Computed, &f("bought")),
+            invested_1y: DateMap::new_bin(1, MapKind::Computed, &f("1y_invested")),
+            stack_1y: DateMap::new_bin(1, MapKind::Computed, &f("1y_stack")),
+            cost_basis_1y: DateMap::new_bin(1, MapKind::Computed, &f("1y_cost_basis")),
+            return_1y: DateMap::new_bin(1, MapKind::Computed, &f("1y_return")),
+            invested_4y: DateMap::new_bin(1, MapKind::Computed, &f("4y_invested")),
+            stack_4y: DateMap::new_bin(1, MapKind::Computed, &f("4y_stack")),
+            cost_basis_4y: DateMap::new_bin(1, MapKind::Computed, &f("4y_cost_basis")),
+            return_4y: DateMap::new_bin(1, MapKind::Computed, &f("4y_return")),
+        };
+
+        s.min_initial_states
+            .consume(MinInitialStates::compute_from_dataset(&s, config));
+
+        Ok(s)
+    }
+
+    /// `should_buy` is the rule of the strategy, called once per date
+    pub fn compute<F>(
+        &mut self,
+        &ComputeData { dates, .. }: &ComputeData,
+        closes: &mut DateMap<f32>,
+        mut should_buy: F,
+    ) where
+        F: FnMut(&Date) -> bool,
+    {
+        self.invested
+            .multi_insert_simple_transform(dates, closes, |close, date| {
+                if close > 0.0 && should_buy(date) {
+                    1.0
+                } else {
+                    0.0
+                }
+            });
+
+        // In sats
+        self.bought.multi_insert_complex_transform(
+            dates,
+            &mut self.invested,
+            |(invested, date, ..)| {
+                let close = closes.get_or_import(date).unwrap();
+
+                if invested > 0.0 {
+                    invested * Amount::ONE_BTC_F64 / close as f64
+                } else {
+                    0.0
+                }
+            },
+        );
+
+        [
+            (
+                &mut self.invested_1y,
+                &mut self.stack_1y,
+                &mut self.cost_basis_1y,
+                &mut self.return_1y,
+                ONE_YEAR_IN_DAYS,
+            ),
+            (
+                &mut self.invested_4y,
+                &mut self.stack_4y,
+                &mut self.cost_basis_4y,
+                &mut self.return_4y,
+                4 * ONE_YEAR_IN_DAYS,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(invested_sum, stack, cost_basis, strategy_return, days)| {
+            invested_sum.multi_insert_last_x_sum(dates, &mut self.invested, days);
+
+            stack.multi_insert_last_x_sum(dates, &mut self.bought, days);
+
+            cost_basis.multi_insert_complex_transform(
+                dates,
+                invested_sum,
+                |(invested_sum, date, ..)| {
+                    let stack = stack.get_or_import(date).unwrap();
+
+                    if stack > 0.0 {
+                        (invested_sum * Amount::ONE_BTC_F64 / stack) as f32
+                    } else {
+                        0.0
+                    }
+                },
+            );
+
+            strategy_return.multi_insert_complex_transform(
+                dates,
+                cost_basis,
+                |(cost_basis, date, ..)| {
+                    let close = closes.get_or_import(date).unwrap();
+
+                    if cost_basis > 0.0 {
+                        (close / cost_basis - 1.0) * 100.0
+                    } else {
+                        0.0
+                    }
+                },
+            );
+        });
+    }
+}
+
+impl AnyDataset for StrategySubDataset {
+    fn get_min_initial_states(&self) -> &MinInitialStates {
+        &self.min_initial_states
+    }
+}
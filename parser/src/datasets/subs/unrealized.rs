@@ -18,6 +18,9 @@ pub struct UnrealizedSubDataset {
     negative_unrealized_loss: BiMap<f32>,
     net_unrealized_profit_and_loss: BiMap<f32>,
     net_unrealized_profit_and_loss_to_market_cap_ratio: BiMap<f32>,
+    own_market_cap: BiMap<f32>,
+    /// NUPL of the cohort
+    net_unrealized_profit_and_loss_to_own_market_cap_ratio: BiMap<f32>,
     supply_in_profit_to_own_supply_ratio: BiMap<f64>,
     supply_in_profit_to_circulating_supply_ratio: BiMap<f64>,
     supply_in_loss_to_own_supply_ratio: BiMap<f64>,
@@ -67,6 +70,12 @@ impl UnrealizedSubDataset {
                 MapKind::Computed,
                 &f("net_unrealized_profit_and_loss_to_market_cap_ratio"),
             ),
+            own_market_cap: BiMap::new_bin(1, MapKind::Computed, &f("own_market_cap")),
+            net_unrealized_profit_and_loss_to_own_market_cap_ratio: BiMap::new_bin(
+                1,
+                MapKind::Computed,
+                &f("net_unrealized_profit_and_loss_to_own_market_cap_ratio"),
+            ),
             supply_in_profit_to_own_supply_ratio: BiMap::new_bin(
                 1,
                 MapKind::Computed,
@@ -138,6 +147,7 @@ impl UnrealizedSubDataset {
     pub fn compute(
         &mut self,
         &ComputeData { heights, dates, .. }: &ComputeData,
+        closes: &mut BiMap<f32>,
         own_supply: &mut BiMap<f64>,
         circulating_supply: &mut BiMap<f64>,
         market_cap: &mut BiMap<f32>,
@@ -171,6 +181,17 @@ impl UnrealizedSubDataset {
                 market_cap,
             );
 
+        self.own_market_cap
+            .multi_insert_multiply(heights, dates, own_supply, closes);
+
+        self.net_unrealized_profit_and_loss_to_own_market_cap_ratio
+            .multi_insert_percentage(
+                heights,
+                dates,
+                &mut self.net_unrealized_profit_and_loss,
+                &mut self.own_market_cap,
+            );
+
         self.supply_in_profit_to_own_supply_ratio
             .multi_insert_percentage(heights, dates, &mut self.supply_in_profit, own_supply);
 
@@ -2,7 +2,10 @@ use allocative::Allocative;
 use struct_iterable::Iterable;
 
 use crate::{
-    datasets::{AnyDataset, ComputeData, InsertData, MinInitialStates, SubDataset},
+    datasets::{
+        AnyDataset, ComputeData, DistributionSubDataset, InsertData, LivelinessSubDataset,
+        MinInitialStates, SubDataset,
+    },
     states::UTXOCohortId,
     structs::{BiMap, Config, Date, Height},
 };
@@ -14,6 +17,10 @@ pub struct UTXODataset {
     min_initial_states: MinInitialStates,
 
     pub subs: SubDataset,
+
+    pub distribution: DistributionSubDataset,
+
+    pub liveliness: LivelinessSubDataset,
 }
 
 impl UTXODataset {
@@ -27,7 +34,9 @@ impl UTXODataset {
         let mut s = Self {
             min_initial_states: MinInitialStates::default(),
             id,
-            subs: SubDataset::import(parent_path, &Some(name), config)?,
+            subs: SubDataset::import(parent_path, &Some(name.clone()), id.can_receive(), config)?,
+            distribution: DistributionSubDataset::import(parent_path, &Some(name.clone()), config)?,
+            liveliness: LivelinessSubDataset::import(parent_path, &Some(name), config)?,
         };
 
         s.min_initial_states
@@ -39,8 +48,9 @@ impl UTXODataset {
     pub fn insert(&mut self, insert_data: &InsertData) {
         let &InsertData {
             states,
+            utxo_cohorts_aging_states,
             utxo_cohorts_one_shot_states,
-            // utxo_cohorts_received_states,
+            utxo_cohorts_received_states,
             utxo_cohorts_sent_states,
             ..
         } = insert_data;
@@ -116,12 +126,36 @@ impl UTXODataset {
                 .insert(insert_data, &utxo_cohorts_sent_states.get(&self.id).input);
         }
 
-        // TODO: move output from common to address
-        // if self.subs.output.needs_insert(insert_data) {
-        //     self.subs
-        //         .output
-        //         .insert(insert_data, utxo_cohorts_received_states.get(&self.id));
-        // }
+        if self.needs_insert_distribution(insert_data.height, insert_data.date) {
+            self.distribution.insert(
+                insert_data,
+                &states
+                    .utxo_cohorts_durable_states
+                    .as_ref()
+                    .unwrap()
+                    .get(&self.id)
+                    .price_to_amount,
+            );
+        }
+
+        if self.needs_insert_liveliness(insert_data.height, insert_data.date) {
+            self.liveliness.insert(
+                insert_data,
+                utxo_cohorts_sent_states.get(&self.id).satdays_destroyed,
+            );
+        }
+
+        if self.needs_insert_output(insert_data.height, insert_data.date) {
+            if let Some(output) = self.subs.output.as_mut() {
+                output.insert(insert_data, utxo_cohorts_received_states.get(&self.id));
+            }
+        }
+
+        if self.needs_insert_aging(insert_data.height, insert_data.date) {
+            if let Some(aging) = self.subs.aging.as_mut() {
+                aging.insert(insert_data, utxo_cohorts_aging_states.get(&self.id));
+            }
+        }
     }
 
     pub fn needs_insert_utxo(&self, height: Height, date: Date) -> bool {
@@ -152,12 +186,35 @@ impl UTXODataset {
         self.subs.input.needs_insert(height, date)
     }
 
+    pub fn needs_insert_output(&self, height: Height, date: Date) -> bool {
+        self.subs
+            .output
+            .as_ref()
+            .is_some_and(|output| output.needs_insert(height, date))
+    }
+
+    pub fn needs_insert_aging(&self, height: Height, date: Date) -> bool {
+        self.subs
+            .aging
+            .as_ref()
+            .is_some_and(|aging| aging.needs_insert(height, date))
+    }
+
+    pub fn needs_insert_liveliness(&self, height: Height, date: Date) -> bool {
+        self.liveliness.needs_insert(height, date)
+    }
+
+    pub fn needs_insert_distribution(&self, height: Height, date: Date) -> bool {
+        self.distribution.needs_insert(height, date)
+    }
+
     pub fn compute(
         &mut self,
         compute_data: &ComputeData,
         closes: &mut BiMap<f32>,
         circulating_supply: &mut BiMap<f64>,
         market_cap: &mut BiMap<f32>,
+        realized_cap: &mut BiMap<f32>,
     ) {
         if self.subs.supply.should_compute(compute_data) {
             self.subs.supply.compute(compute_data, circulating_supply);
@@ -166,6 +223,7 @@ impl UTXODataset {
         if self.subs.unrealized.should_compute(compute_data) {
             self.subs.unrealized.compute(
                 compute_data,
+                closes,
                 &mut self.subs.supply.supply,
                 circulating_supply,
                 market_cap,
@@ -177,16 +235,24 @@ impl UTXODataset {
         }
 
         if self.subs.capitalization.should_compute(compute_data) {
-            self.subs
-                .capitalization
-                .compute(compute_data, closes, &mut self.subs.supply.supply);
+            self.subs.capitalization.compute(
+                compute_data,
+                closes,
+                &mut self.subs.supply.supply,
+                realized_cap,
+            );
+        }
+
+        if self.liveliness.should_compute(compute_data) {
+            self.liveliness
+                .compute(compute_data, &mut self.subs.supply.supply);
         }
 
-        // if self.subs.output.should_compute(compute_data) {
-        //     self.subs
-        //         .output
-        //         .compute(compute_data, &mut self.subs.supply.total);
-        // }
+        if let Some(output) = self.subs.output.as_mut() {
+            if output.should_compute(compute_data) {
+                output.compute(compute_data, &mut self.subs.supply.supply);
+            }
+        }
     }
 }
 
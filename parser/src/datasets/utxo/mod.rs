@@ -18,7 +18,7 @@ use super::{AnyDataset, ComputeData, InsertData, MinInitialStates};
 pub struct UTXODatasets {
     min_initial_states: MinInitialStates,
 
-    cohorts: SplitByUTXOCohort<UTXODataset>,
+    pub cohorts: SplitByUTXOCohort<UTXODataset>,
 }
 
 impl UTXODatasets {
@@ -60,11 +60,13 @@ impl UTXODatasets {
         let needs_insert_capitalization = self.needs_insert_capitalization(height, date);
         let needs_insert_supply = self.needs_insert_supply(height, date);
         let needs_one_shot_states = self.needs_one_shot_states(height, date);
+        let needs_insert_distribution = self.needs_insert_distribution(height, date);
 
         needs_insert_utxo
             || needs_insert_capitalization
             || needs_insert_supply
             || needs_one_shot_states
+            || needs_insert_distribution
     }
 
     pub fn needs_one_shot_states(&self, height: Height, date: Date) -> bool {
@@ -72,7 +74,17 @@ impl UTXODatasets {
     }
 
     pub fn needs_sent_states(&self, height: Height, date: Date) -> bool {
-        self.needs_insert_input(height, date) || self.needs_insert_realized(height, date)
+        self.needs_insert_input(height, date)
+            || self.needs_insert_realized(height, date)
+            || self.needs_insert_liveliness(height, date)
+    }
+
+    pub fn needs_received_states(&self, height: Height, date: Date) -> bool {
+        self.needs_insert_output(height, date)
+    }
+
+    pub fn needs_aging_states(&self, height: Height, date: Date) -> bool {
+        self.needs_insert_aging(height, date)
     }
 
     pub fn needs_insert_utxo(&self, height: Height, date: Date) -> bool {
@@ -117,18 +129,49 @@ impl UTXODatasets {
             .any(|(dataset, _)| dataset.needs_insert_input(height, date))
     }
 
+    pub fn needs_insert_output(&self, height: Height, date: Date) -> bool {
+        self.as_vec()
+            .iter()
+            .any(|(dataset, _)| dataset.needs_insert_output(height, date))
+    }
+
+    pub fn needs_insert_aging(&self, height: Height, date: Date) -> bool {
+        self.as_vec()
+            .iter()
+            .any(|(dataset, _)| dataset.needs_insert_aging(height, date))
+    }
+
+    pub fn needs_insert_liveliness(&self, height: Height, date: Date) -> bool {
+        self.as_vec()
+            .iter()
+            .any(|(dataset, _)| dataset.needs_insert_liveliness(height, date))
+    }
+
+    pub fn needs_insert_distribution(&self, height: Height, date: Date) -> bool {
+        self.as_vec()
+            .iter()
+            .any(|(dataset, _)| dataset.needs_insert_distribution(height, date))
+    }
+
     pub fn compute(
         &mut self,
         compute_data: &ComputeData,
         closes: &mut BiMap<f32>,
         circulating_supply: &mut BiMap<f64>,
         market_cap: &mut BiMap<f32>,
+        realized_cap: &mut BiMap<f32>,
     ) {
         self.cohorts
             .as_mut_vec()
             .into_iter()
             .for_each(|(cohort, _)| {
-                cohort.compute(compute_data, closes, circulating_supply, market_cap)
+                cohort.compute(
+                    compute_data,
+                    closes,
+                    circulating_supply,
+                    market_cap,
+                    realized_cap,
+                )
             })
     }
 
@@ -10,6 +10,8 @@ use bincode::{
 };
 use zstd::decode_all;
 
+use super::Checkpoint;
+
 const ZST_EXTENSION: &str = "zst";
 
 pub const BIN_EXTENSION: &str = "bin";
@@ -71,7 +73,11 @@ impl Binary {
     {
         // log(&format!("Exporting: {:?}", path));
 
-        match Self::type_from_path(path) {
+        let binary_type = Self::type_from_path(path);
+
+        let path = &Checkpoint::stage(path);
+
+        match binary_type {
             BinaryType::Compressed => Self::export_compressed(path, value),
             BinaryType::Raw => Self::export_raw(path, value),
         }
@@ -0,0 +1,347 @@
+use std::{
+    collections::BTreeSet,
+    fs::{self, File, OpenOptions},
+    io::{self, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    structs::{Date, Height},
+    utils::log,
+};
+
+use super::OUTPUTS_FOLDER_PATH;
+
+const PENDING_EXTENSION: &str = "pending";
+const JOURNAL_EXTENSION: &str = "journal";
+const MANIFEST_FILE_NAME: &str = "checkpoint.json";
+
+/// Files written and removed since the beginning of the current checkpoint, `None` when there is none
+static STAGING: Mutex<Option<Staging>> = Mutex::new(None);
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Staging {
+    written: BTreeSet<PathBuf>,
+    removed: BTreeSet<PathBuf>,
+    /// Files written in place, their writes being recorded in a journal until the commit
+    #[serde(default)]
+    journaled: BTreeSet<PathBuf>,
+}
+
+/// Write-ahead manifest
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    height: Height,
+    date: Date,
+    prepared: bool,
+    staging: Staging,
+}
+
+///
+/// Two-phase commit of everything that is exported (datasets, databases metadata and write-ahead logs, states).
+///
+/// While a checkpoint is open, every file is written next to its final path with a `.pending` extension.
+/// Files updated in place (columnar datasets) get their writes appended to a `.journal` file instead.
+/// Committing fsyncs them, records them in a prepared manifest (the commit point) and then renames them or replays the journals.
+///
+/// After a crash, `recover` finishes the renames if the manifest was prepared and ignores the pending files otherwise,
+/// so either the old or the new state survives, never a mix of both.
+///
+pub struct Checkpoint {
+    height: Height,
+    date: Date,
+    manifest_path: PathBuf,
+    committed: bool,
+}
+
+/// Checkpoint past its commit point, only the renames and the journal replays are left
+pub struct PreparedCheckpoint {
+    manifest: Manifest,
+    manifest_path: PathBuf,
+}
+
+impl Checkpoint {
+    pub fn begin(height: Height, date: Date) -> color_eyre::Result<Self> {
+        Self::begin_in(Path::new(OUTPUTS_FOLDER_PATH), height, date)
+    }
+
+    /// `folder` is where the manifest is written
+    pub fn begin_in(folder: &Path, height: Height, date: Date) -> color_eyre::Result<Self> {
+        let manifest_path = folder.join(MANIFEST_FILE_NAME);
+
+        Self::write_manifest(
+            &manifest_path,
+            &Manifest {
+                height,
+                date,
+                prepared: false,
+                staging: Staging::default(),
+            },
+        )?;
+
+        STAGING.lock().unwrap().replace(Staging::default());
+
+        Ok(Self {
+            height,
+            date,
+            manifest_path,
+            committed: false,
+        })
+    }
+
+    pub fn commit(self) -> color_eyre::Result<()> {
+        self.prepare()?.finish()
+    }
+
+    /// Syncs the staged files and writes the prepared manifest, the commit point
+    pub fn prepare(mut self) -> color_eyre::Result<PreparedCheckpoint> {
+        let staging = STAGING.lock().unwrap().take().unwrap_or_default();
+
+        staging
+            .written
+            .iter()
+            .try_for_each(|path| File::open(Self::pending_path(path))?.sync_all())?;
+
+        staging
+            .journaled
+            .iter()
+            .try_for_each(|path| File::open(Self::journal_path(path))?.sync_all())?;
+
+        let manifest = Manifest {
+            height: self.height,
+            date: self.date,
+            prepared: true,
+            staging,
+        };
+
+        Self::write_manifest(&self.manifest_path, &manifest)?;
+
+        self.committed = true;
+
+        Ok(PreparedCheckpoint {
+            manifest,
+            manifest_path: self.manifest_path.clone(),
+        })
+    }
+
+    /// Needs to be called before importing anything
+    pub fn recover() -> color_eyre::Result<()> {
+        Self::recover_in(Path::new(OUTPUTS_FOLDER_PATH))
+    }
+
+    /// `folder` is where the manifest was written
+    pub fn recover_in(folder: &Path) -> color_eyre::Result<()> {
+        let manifest_path = folder.join(MANIFEST_FILE_NAME);
+
+        if !manifest_path.exists() {
+            return Ok(());
+        }
+
+        let manifest: Manifest = serde_json::from_slice(&fs::read(&manifest_path)?)?;
+
+        if manifest.prepared {
+            log(&format!(
+                "Finishing interrupted checkpoint of height {} ({})",
+                manifest.height, manifest.date
+            ));
+
+            PreparedCheckpoint {
+                manifest,
+                manifest_path,
+            }
+            .finish()
+        } else {
+            log(&format!(
+                "Discarding interrupted checkpoint of height {} ({})",
+                manifest.height, manifest.date
+            ));
+
+            fs::remove_file(manifest_path)?;
+
+            Ok(())
+        }
+    }
+
+    /// Returns the path where the file should be written, the pending one if a checkpoint is open
+    pub fn stage(path: &Path) -> PathBuf {
+        if let Some(staging) = STAGING.lock().unwrap().as_mut() {
+            staging.removed.remove(path);
+            staging.written.insert(path.to_owned());
+            Self::pending_path(path)
+        } else {
+            path.to_owned()
+        }
+    }
+
+    /// Writes the bytes at the offset of the file right away or when the checkpoint is committed if one is open
+    pub fn write_at(path: &Path, offset: u64, bytes: &[u8]) -> io::Result<()> {
+        let mut staging = STAGING.lock().unwrap();
+
+        let Some(staging) = staging.as_mut() else {
+            let mut file = OpenOptions::new()
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(path)?;
+
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(bytes)?;
+
+            return file.sync_data();
+        };
+
+        let journal_path = Self::journal_path(path);
+
+        // A journal left by a discarded checkpoint is started over
+        let mut journal = if staging.journaled.insert(path.to_owned()) {
+            File::create(journal_path)?
+        } else {
+            OpenOptions::new().append(true).open(journal_path)?
+        };
+
+        let mut entry = Vec::with_capacity(16 + bytes.len());
+        entry.extend(offset.to_le_bytes());
+        entry.extend((bytes.len() as u64).to_le_bytes());
+        entry.extend(bytes);
+
+        journal.write_all(&entry)
+    }
+
+    /// Removes the file right away or when the checkpoint is committed if one is open
+    pub fn remove_file(path: &Path) -> io::Result<()> {
+        if let Some(staging) = STAGING.lock().unwrap().as_mut() {
+            if path.exists() {
+                staging.removed.insert(path.to_owned());
+            }
+            Ok(())
+        } else {
+            fs::remove_file(path)
+        }
+    }
+
+    /// Idempotent, the journal is only removed once its writes are synced
+    fn replay_journal(path: &Path) -> color_eyre::Result<()> {
+        let journal_path = Self::journal_path(path);
+
+        if !journal_path.exists() {
+            return Ok(());
+        }
+
+        let journal = fs::read(&journal_path)?;
+
+        let mut file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+
+        let mut rest = journal.as_slice();
+
+        while !rest.is_empty() {
+            let offset = u64::from_le_bytes(rest[0..8].try_into()?);
+            let len = u64::from_le_bytes(rest[8..16].try_into()?) as usize;
+
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&rest[16..16 + len])?;
+
+            rest = &rest[16 + len..];
+        }
+
+        file.sync_data()?;
+
+        fs::remove_file(journal_path)?;
+
+        Ok(())
+    }
+
+    fn write_manifest(path: &Path, manifest: &Manifest) -> color_eyre::Result<()> {
+        let tmp_path = path.with_extension("tmp");
+
+        let file = File::create(&tmp_path)?;
+        serde_json::to_writer(&file, manifest)?;
+        file.sync_all()?;
+
+        fs::rename(&tmp_path, path)?;
+
+        // Make the rename itself durable
+        Self::sync_folder(path.parent().unwrap())?;
+
+        Ok(())
+    }
+
+    fn pending_path(path: &Path) -> PathBuf {
+        PathBuf::from(format!("{}.{PENDING_EXTENSION}", path.to_str().unwrap()))
+    }
+
+    fn journal_path(path: &Path) -> PathBuf {
+        PathBuf::from(format!("{}.{JOURNAL_EXTENSION}", path.to_str().unwrap()))
+    }
+
+    fn sync_folder(folder: &Path) -> io::Result<()> {
+        if folder.as_os_str().is_empty() {
+            File::open(".")?.sync_all()
+        } else {
+            File::open(folder)?.sync_all()
+        }
+    }
+}
+
+impl PreparedCheckpoint {
+    /// Idempotent
+    pub fn finish(self) -> color_eyre::Result<()> {
+        let Self {
+            manifest,
+            manifest_path,
+        } = self;
+
+        manifest.staging.written.iter().try_for_each(|path| {
+            let pending_path = Checkpoint::pending_path(path);
+
+            if pending_path.exists() {
+                fs::rename(pending_path, path)
+            } else {
+                Ok(())
+            }
+        })?;
+
+        manifest
+            .staging
+            .journaled
+            .iter()
+            .try_for_each(|path| Checkpoint::replay_journal(path))?;
+
+        manifest
+            .staging
+            .removed
+            .iter()
+            .filter(|path| path.exists())
+            .try_for_each(fs::remove_file)?;
+
+        // Make the renames and removals durable before forgetting about them
+        manifest
+            .staging
+            .written
+            .iter()
+            .chain(&manifest.staging.removed)
+            .filter_map(|path| path.parent())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .try_for_each(Checkpoint::sync_folder)?;
+
+        fs::remove_file(manifest_path)?;
+
+        Ok(())
+    }
+}
+
+impl Drop for Checkpoint {
+    /// An export that failed midway leaves the previous state untouched
+    fn drop(&mut self) {
+        if !self.committed {
+            STAGING.lock().unwrap().take();
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::{fmt::Debug, fs::File, ops::Range, path::Path};
+
+use bincode::{config, decode_from_slice, encode_to_vec, Decode, Encode};
+use color_eyre::eyre::eyre;
+use memmap2::Mmap;
+
+use super::Checkpoint;
+
+pub const COLUMNAR_EXTENSION: &str = "col";
+pub const COLUMNAR_FILE_NAME: &str = "values.col";
+
+/// Version and width of the slots, both as little endian u32
+const HEADER_LEN: usize = 8;
+
+/// First byte of the slot of a key that has a value, the holes left between written values are zeroes
+const PRESENT: u8 = 1;
+
+///
+/// One file per map with every value encoded with the same width, one after the other.
+///
+/// The slot of the key `i` (height or number of days since genesis) is thus at `HEADER_LEN + i * width`,
+/// which makes reading any key O(1) without decoding anything else.
+/// Each slot starts with a byte telling if the key has a value, keys without one (dates without blocks, maps starting late, ...) being left zeroed.
+///
+/// Not compressed and written in place, through the journal of the checkpoint when one is open
+/// so that a crash midway through an export doesn't leave values newer than the states.
+///
+pub struct Columnar {
+    mmap: Mmap,
+    version: u32,
+    width: usize,
+}
+
+impl Debug for Columnar {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Columnar")
+            .field("version", &self.version)
+            .field("width", &self.width)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Columnar {
+    /// Width of the slots, the presence byte plus the encoded value, every `MapValue` has a fixed width with fixed int encoding
+    pub fn width<T>() -> usize
+    where
+        T: Encode + Default,
+    {
+        1 + encode_to_vec(T::default(), config::standard().with_fixed_int_encoding())
+            .unwrap()
+            .len()
+    }
+
+    pub fn open(path: &Path) -> color_eyre::Result<Self> {
+        let file = File::open(path)?;
+
+        let mmap = unsafe { Mmap::map(&file)? };
+
+        if mmap.len() < HEADER_LEN {
+            return Err(eyre!("Missing header in {path:?}"));
+        }
+
+        let version = u32::from_le_bytes(mmap[0..4].try_into().unwrap());
+        let width = u32::from_le_bytes(mmap[4..8].try_into().unwrap()) as usize;
+
+        if width == 0 {
+            return Err(eyre!("Zero width in {path:?}"));
+        }
+
+        Ok(Self {
+            mmap,
+            version,
+            width,
+        })
+    }
+
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Number of slots, with or without a value, a slot cut short by a crash isn't counted
+    pub fn len(&self) -> usize {
+        (self.mmap.len() - HEADER_LEN) / self.width
+    }
+
+    /// `None` if the key has no value
+    pub fn get<T>(&self, index: usize) -> color_eyre::Result<Option<T>>
+    where
+        T: Encode + Decode + Default,
+    {
+        if index >= self.len() {
+            return Ok(None);
+        }
+
+        self.check_width::<T>()?;
+
+        self.decode(index)
+    }
+
+    /// Truncated to the slots present, `None` for the keys without a value
+    pub fn range<T>(&self, range: Range<usize>) -> color_eyre::Result<Vec<Option<T>>>
+    where
+        T: Encode + Decode + Default,
+    {
+        self.check_width::<T>()?;
+
+        (range.start..range.end.min(self.len()))
+            .map(|index| self.decode(index))
+            .collect()
+    }
+
+    /// Index and value of the last key with a value
+    pub fn last<T>(&self) -> color_eyre::Result<Option<(usize, T)>>
+    where
+        T: Encode + Decode + Default,
+    {
+        self.check_width::<T>()?;
+
+        for index in (0..self.len()).rev() {
+            if let Some(value) = self.decode(index)? {
+                return Ok(Some((index, value)));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn decode<T>(&self, index: usize) -> color_eyre::Result<Option<T>>
+    where
+        T: Decode,
+    {
+        let start = HEADER_LEN + index * self.width;
+
+        if self.mmap[start] != PRESENT {
+            return Ok(None);
+        }
+
+        let (value, _) = decode_from_slice(
+            &self.mmap[start + 1..start + self.width],
+            config::standard().with_fixed_int_encoding(),
+        )?;
+
+        Ok(Some(value))
+    }
+
+    fn check_width<T>(&self) -> color_eyre::Result<()>
+    where
+        T: Encode + Default,
+    {
+        let width = Self::width::<T>();
+
+        if width != self.width {
+            return Err(eyre!("Expected a width of {width}, found {}", self.width));
+        }
+
+        Ok(())
+    }
+
+    /// Writes consecutive values starting at `first_index` and marks them as present, creating the file if needed
+    pub fn write<T>(
+        path: &Path,
+        version: u32,
+        first_index: usize,
+        values: &[T],
+    ) -> color_eyre::Result<()>
+    where
+        T: Encode + Default,
+    {
+        let width = Self::width::<T>();
+
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend(version.to_le_bytes());
+        header.extend((width as u32).to_le_bytes());
+
+        Checkpoint::write_at(path, 0, &header)?;
+
+        let config = config::standard().with_fixed_int_encoding();
+
+        let mut bytes = Vec::with_capacity(values.len() * width);
+
+        values
+            .iter()
+            .try_for_each(|value| -> color_eyre::Result<()> {
+                bytes.push(PRESENT);
+                bytes.extend(encode_to_vec(value, config)?);
+                Ok(())
+            })?;
+
+        Checkpoint::write_at(path, (HEADER_LEN + first_index * width) as u64, &bytes)?;
+
+        Ok(())
+    }
+}
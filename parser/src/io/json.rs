@@ -6,6 +6,8 @@ use std::{
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use super::Checkpoint;
+
 pub struct Json;
 
 pub const JSON_EXTENSION: &str = "json";
@@ -36,6 +38,8 @@ impl Json {
             panic!("Wrong extension");
         }
 
+        let path = &Checkpoint::stage(path);
+
         let file = File::create(path).unwrap_or_else(|_| {
             dbg!(&path);
             panic!("No such file or directory")
@@ -0,0 +1,41 @@
+use std::{
+    fs::{self, File, TryLockError},
+    path::Path,
+};
+
+use color_eyre::eyre::eyre;
+
+use super::OUTPUTS_FOLDER_PATH;
+
+const LOCK_FILE_NAME: &str = "parser.lock";
+
+///
+/// Exclusive lock on the outputs folder, held by the parser for as long as it runs and by the snapshot commands.
+///
+/// The os releases it when the process exits, even after a crash, so a leftover lock file doesn't need to be removed.
+///
+pub struct OutputsLock {
+    _file: File,
+}
+
+impl OutputsLock {
+    pub fn acquire() -> color_eyre::Result<Self> {
+        fs::create_dir_all(OUTPUTS_FOLDER_PATH)?;
+
+        let path = Path::new(OUTPUTS_FOLDER_PATH).join(LOCK_FILE_NAME);
+
+        let file = File::options()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)?;
+
+        match file.try_lock() {
+            Ok(()) => Ok(Self { _file: file }),
+            Err(TryLockError::WouldBlock) => Err(eyre!(
+                "{path:?} is locked, another parser or snapshot command is running"
+            )),
+            Err(TryLockError::Error(error)) => Err(error.into()),
+        }
+    }
+}
@@ -1,9 +1,15 @@
 mod binary;
+mod checkpoint;
+mod columnar;
 mod consts;
 mod json;
+mod lock;
 mod serialization;
 
 pub use binary::*;
+pub use checkpoint::*;
+pub use columnar::*;
 pub use consts::*;
 pub use json::*;
+pub use lock::*;
 pub use serialization::*;
@@ -1,4 +1,4 @@
-use std::{fmt::Debug, fs, path::Path};
+use std::{fmt::Debug, path::Path};
 
 use allocative::Allocative;
 use bincode::{Decode, Encode};
@@ -7,13 +7,18 @@ use serde::{de::DeserializeOwned, Serialize};
 
 use crate::io::{Binary, Json};
 
-use super::{BIN_EXTENSION, COMPRESSED_BIN_EXTENSION, HAR_EXTENSION, JSON_EXTENSION};
+use super::{
+    Checkpoint, BIN_EXTENSION, COLUMNAR_EXTENSION, COMPRESSED_BIN_EXTENSION, HAR_EXTENSION,
+    JSON_EXTENSION,
+};
 
 #[derive(PartialEq, PartialOrd, Ord, Eq, Debug, Clone, Copy, Default, Allocative)]
 pub enum Serialization {
     #[default]
     Binary,
     Json,
+    /// Can't be imported or exported whole, see `GenericMap`
+    Columnar,
 }
 
 impl Serialization {
@@ -24,6 +29,7 @@ impl Serialization {
                 path.ends_with(BIN_EXTENSION) || path.ends_with(COMPRESSED_BIN_EXTENSION)
             }
             Self::Json => path.ends_with(JSON_EXTENSION) || path.ends_with(HAR_EXTENSION),
+            Self::Columnar => path.ends_with(COLUMNAR_EXTENSION),
         }
     }
 
@@ -33,6 +39,8 @@ impl Serialization {
             Self::Binary
         } else if path.ends_with(JSON_EXTENSION) || path.ends_with(HAR_EXTENSION) {
             Self::Json
+        } else if path.ends_with(COLUMNAR_EXTENSION) {
+            Self::Columnar
         } else {
             panic!("Extension \"{path}\" isn't supported")
         }
@@ -80,6 +88,7 @@ impl Serialization {
                     Err(eyre!("Wrong path or no file"))
                 }
             }
+            Serialization::Columnar => Err(eyre!("Columnar files are read by key or by chunk")),
         }
     }
 
@@ -100,7 +109,10 @@ impl Serialization {
                     );
 
                     if res.is_ok() {
-                        let _ = fs::remove_file(Path::new(&format!("{}.{BIN_EXTENSION}", path)));
+                        let _ = Checkpoint::remove_file(Path::new(&format!(
+                            "{}.{BIN_EXTENSION}",
+                            path
+                        )));
                     }
 
                     res
@@ -116,6 +128,7 @@ impl Serialization {
                     )
                 }
             }
+            Serialization::Columnar => Err(eyre!("Columnar files are written by key")),
         }
     }
 }
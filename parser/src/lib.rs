@@ -8,12 +8,16 @@ mod structs;
 mod utils;
 
 pub use crate::{
-    actions::iter_blocks,
-    databases::{AnyDatabase, Database},
-    io::{Binary, Json, Serialization, COMPRESSED_BIN_EXTENSION, JSON_EXTENSION},
+    actions::{create_snapshot, iter_blocks, restore_snapshot, verify},
+    databases::{AnyDatabase, Database, Databases, RECORDING_FOLDER_PATH},
+    io::{
+        Binary, Json, OutputsLock, Serialization, COLUMNAR_FILE_NAME, COMPRESSED_BIN_EXTENSION,
+        JSON_EXTENSION,
+    },
     structs::{
-        Amount, Config, Date, DateMap, Exit, Height, HeightMap, MapChunkId, MapValue,
-        SerializedBTreeMap, SerializedVec, TxoutIndex, HEIGHT_MAP_CHUNK_SIZE, OHLC,
+        Amount, Command, Config, Date, DateMap, DateMapChunkId, Distribution, Exit, Height,
+        HeightMap, HeightMapChunkId, MapChunkId, MapValue, SerializedBTreeMap, SerializedVec,
+        SnapshotAction, TxoutIndex, HEIGHT_MAP_CHUNK_SIZE, OHLC,
     },
     utils::{create_rpc, log, reset_logs},
 };
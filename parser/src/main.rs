@@ -1,7 +1,10 @@
 use std::{thread::sleep, time::Duration};
 
 use biter::bitcoincore_rpc::RpcApi;
-use parser::{create_rpc, iter_blocks, log, reset_logs, Config, Exit};
+use parser::{
+    create_rpc, create_snapshot, iter_blocks, log, reset_logs, restore_snapshot, verify, Command,
+    Config, Exit, OutputsLock, SnapshotAction,
+};
 
 fn main() -> color_eyre::Result<()> {
     color_eyre::install()?;
@@ -10,8 +13,28 @@ fn main() -> color_eyre::Result<()> {
 
     let mut config = Config::import()?;
 
+    if let Some(Command::Verify) = config.command {
+        let sound = verify(&config)?;
+
+        std::process::exit(if sound { 0 } else { 1 });
+    }
+
     let rpc = create_rpc(&config).unwrap();
 
+    // Held until the process exits
+    let lock = OutputsLock::acquire()?;
+
+    if let Some(Command::Snapshot { action }) = &config.command {
+        match action {
+            SnapshotAction::Create { path, height } => {
+                create_snapshot(&rpc, path, *height, &lock)?;
+
+                return Ok(());
+            }
+            SnapshotAction::Restore { path } => restore_snapshot(&rpc, path, &lock)?,
+        }
+    }
+
     let exit = Exit::new();
 
     loop {
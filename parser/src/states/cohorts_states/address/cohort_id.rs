@@ -23,6 +23,8 @@ pub enum AddressCohortId {
     P2WPKH,
     P2WSH,
     P2TR,
+
+    RevealedPubkey,
 }
 
 impl AddressCohortId {
@@ -49,6 +51,8 @@ impl AddressCohortId {
             Self::P2WPKH => Some("p2wpkh"),
             Self::P2WSH => Some("p2wsh"),
             Self::P2TR => Some("p2tr"),
+
+            Self::RevealedPubkey => Some("revealed_pubkey"),
         }
     }
 
@@ -75,6 +79,8 @@ impl AddressCohortId {
             Self::P2WPKH => AddressSplit::Type(AddressType::P2WPKH),
             Self::P2WSH => AddressSplit::Type(AddressType::P2WSH),
             Self::P2TR => AddressSplit::Type(AddressType::P2TR),
+
+            Self::RevealedPubkey => AddressSplit::RevealedPubkey,
         }
     }
 }
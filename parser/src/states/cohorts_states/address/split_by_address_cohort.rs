@@ -29,6 +29,8 @@ pub struct SplitByAddressCohort<T> {
     pub p2wpkh: T,
     pub p2wsh: T,
     pub p2tr: T,
+
+    pub revealed_pubkey: T,
 }
 
 impl<T> SplitByAddressCohort<T> {
@@ -67,6 +69,8 @@ impl<T> SplitByAddressCohort<T> {
                 AddressSize::Megalodon => Some(&self.megalodon),
                 AddressSize::Empty => None,
             },
+
+            AddressSplit::RevealedPubkey => Some(&self.revealed_pubkey),
         }
     }
 
@@ -101,6 +105,13 @@ impl<T> SplitByAddressCohort<T> {
             normal_iteration(state)?;
         }
 
+        if address_data.has_revealed_pubkey() {
+            normal_iteration(
+                self.get_mut_from_split(&AddressSplit::RevealedPubkey)
+                    .unwrap(),
+            )?;
+        }
+
         Ok(())
     }
 
@@ -139,6 +150,8 @@ impl<T> SplitByAddressCohort<T> {
                 AddressSize::Megalodon => Some(&mut self.megalodon),
                 AddressSize::Empty => None,
             },
+
+            AddressSplit::RevealedPubkey => Some(&mut self.revealed_pubkey),
         }
     }
 
@@ -165,6 +178,8 @@ impl<T> SplitByAddressCohort<T> {
             AddressCohortId::P2WPKH => &mut self.p2wpkh,
             AddressCohortId::P2WSH => &mut self.p2wsh,
             AddressCohortId::P2TR => &mut self.p2tr,
+
+            AddressCohortId::RevealedPubkey => &mut self.revealed_pubkey,
         }
     }
 
@@ -188,6 +203,7 @@ impl<T> SplitByAddressCohort<T> {
             (&self.p2wpkh, AddressCohortId::P2WPKH),
             (&self.p2wsh, AddressCohortId::P2WSH),
             (&self.p2tr, AddressCohortId::P2TR),
+            (&self.revealed_pubkey, AddressCohortId::RevealedPubkey),
         ]
     }
 
@@ -211,6 +227,7 @@ impl<T> SplitByAddressCohort<T> {
             (&mut self.p2wpkh, AddressCohortId::P2WPKH),
             (&mut self.p2wsh, AddressCohortId::P2WSH),
             (&mut self.p2tr, AddressCohortId::P2TR),
+            (&mut self.revealed_pubkey, AddressCohortId::RevealedPubkey),
         ]
     }
 }
@@ -241,6 +258,8 @@ where
         self.p2wpkh += rhs.p2wpkh;
         self.p2wsh += rhs.p2wsh;
         self.p2tr += rhs.p2tr;
+
+        self.revealed_pubkey += rhs.revealed_pubkey;
     }
 }
 
@@ -268,6 +287,8 @@ impl<T> SplitByAddressCohort<Option<T>> {
             p2wpkh: self.p2wpkh.unwrap(),
             p2wsh: self.p2wsh.unwrap(),
             p2tr: self.p2tr.unwrap(),
+
+            revealed_pubkey: self.revealed_pubkey.unwrap(),
         }
     }
 }
@@ -7,13 +7,13 @@ pub struct OutputState {
 }
 
 impl OutputState {
-    // pub fn count(&self) -> f64 {
-    //     self.count
-    // }
+    pub fn count(&self) -> f64 {
+        self.count
+    }
 
-    // pub fn volume(&self) -> Amount {
-    //     self.volume
-    // }
+    pub fn volume(&self) -> Amount {
+        self.volume
+    }
 
     pub fn iterate(&mut self, count: f64, volume: Amount) {
         self.count += count;
@@ -1,5 +1,7 @@
 use allocative::Allocative;
 
+use super::{UTXOCheck, UTXOFilter, UTXO_FILTERS};
+
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Allocative)]
 pub enum UTXOCohortId {
     #[default]
@@ -48,6 +50,19 @@ pub enum UTXOCohortId {
 }
 
 impl UTXOCohortId {
+    ///
+    /// Whether new outputs, which are 0 days old, can be part of the cohort.
+    ///
+    /// Older age cohorts (lth, from_1d_to_1w, ..., from_15y) only grow by aging, an output is never received directly into them.
+    /// They have an aging dataset, with the supply crossing into them, instead of an output one.
+    ///
+    pub fn can_receive(&self) -> bool {
+        match UTXO_FILTERS.get(self) {
+            UTXOFilter::Epoch(_) => true,
+            filter => filter.check_days_old(&0),
+        }
+    }
+
     pub fn name(&self) -> &str {
         match self {
             Self::UpTo1d => "up_to_1d",
@@ -0,0 +1,53 @@
+use derive_deref::{Deref, DerefMut};
+
+use crate::{
+    states::{DateDataVec, OutputState},
+    structs::{Amount, BlockData, Timestamp},
+};
+
+use super::SplitByUTXOCohort;
+
+#[derive(Deref, DerefMut, Default)]
+pub struct UTXOCohortsAgingStates(SplitByUTXOCohort<OutputState>);
+
+impl UTXOCohortsAgingStates {
+    /// Unspent outputs that crossed into an age cohort since the previous block, like the ones turning 155 days old for the lth
+    pub fn compute(
+        &mut self,
+        date_data_vec: &DateDataVec,
+        last_block_data: &BlockData,
+        previous_last_block_data: &BlockData,
+    ) {
+        date_data_vec
+            .iter()
+            .flat_map(|date_data| &date_data.blocks)
+            .filter(|block_data| {
+                block_data.height != last_block_data.height && block_data.amount != Amount::ZERO
+            })
+            .for_each(|block_data| {
+                let days_old = Timestamp::difference_in_days_between(
+                    block_data.timestamp,
+                    last_block_data.timestamp,
+                );
+
+                let previous_days_old = Timestamp::difference_in_days_between(
+                    block_data.timestamp,
+                    previous_last_block_data.timestamp,
+                );
+
+                if days_old == previous_days_old {
+                    return;
+                }
+
+                let utxo_count = block_data.utxos as f64;
+                let amount = block_data.amount;
+
+                self.duo_filtered_apply(
+                    &days_old,
+                    &previous_days_old,
+                    |state| state.iterate(utxo_count, amount),
+                    |_| {},
+                );
+            });
+    }
+}
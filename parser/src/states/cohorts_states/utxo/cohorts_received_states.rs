@@ -0,0 +1,20 @@
+use derive_deref::{Deref, DerefMut};
+
+use crate::{
+    states::OutputState,
+    structs::{Height, ReceivedData},
+};
+
+use super::SplitByUTXOCohort;
+
+#[derive(Deref, DerefMut, Default)]
+pub struct UTXOCohortsReceivedStates(SplitByUTXOCohort<OutputState>);
+
+impl UTXOCohortsReceivedStates {
+    /// Outputs are all created in the current block, thus 0 days old
+    pub fn compute(&mut self, height: Height, received_data: &ReceivedData) {
+        self.initial_filtered_apply(&0, &height, |state| {
+            state.iterate(received_data.count as f64, received_data.volume);
+        });
+    }
+}
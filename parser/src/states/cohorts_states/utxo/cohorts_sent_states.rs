@@ -4,7 +4,7 @@ use derive_deref::{Deref, DerefMut};
 
 use crate::{
     states::{DateDataVec, InputState, RealizedState},
-    structs::{BlockPath, Price, SentData, Timestamp},
+    structs::{Amount, BlockPath, Price, SentData, Timestamp},
 };
 
 use super::SplitByUTXOCohort;
@@ -13,6 +13,7 @@ use super::SplitByUTXOCohort;
 pub struct SentState {
     pub input: InputState,
     pub realized: RealizedState,
+    pub satdays_destroyed: Amount,
 }
 
 #[derive(Deref, DerefMut, Default)]
@@ -48,6 +49,8 @@ impl UTXOCohortsSentStates {
                     self.initial_filtered_apply(&days_old, &height, |state| {
                         state.input.iterate(sent_data.count as f64, amount_sent);
 
+                        state.satdays_destroyed += amount_sent * days_old as u64;
+
                         let previous_value = previous_price * amount_sent;
                         let current_value = current_price * amount_sent;
 
@@ -2,8 +2,10 @@ mod cohort_durable_states;
 mod cohort_filter;
 mod cohort_filters;
 mod cohort_id;
+mod cohorts_aging_states;
 mod cohorts_durable_states;
 mod cohorts_one_shot_states;
+mod cohorts_received_states;
 mod cohorts_sent_states;
 mod split_by_utxo_cohort;
 
@@ -11,7 +13,9 @@ pub use cohort_durable_states::*;
 pub use cohort_filter::*;
 pub use cohort_filters::*;
 pub use cohort_id::*;
+pub use cohorts_aging_states::*;
 pub use cohorts_durable_states::*;
 pub use cohorts_one_shot_states::*;
+pub use cohorts_received_states::*;
 pub use cohorts_sent_states::*;
 pub use split_by_utxo_cohort::*;
@@ -3,7 +3,10 @@ use bincode::{Decode, Encode};
 use derive_deref::{Deref, DerefMut};
 use serde::{Deserialize, Serialize};
 
-use crate::structs::{BlockData, BlockPath, DateData};
+use crate::{
+    structs::{Amount, BlockData, BlockPath, DateData, Timestamp},
+    utils::ONE_DAY_IN_S,
+};
 
 use super::AnyState;
 
@@ -37,6 +40,31 @@ impl DateDataVec {
             .get(block_path.date_index as usize)
             .and_then(|date_data| date_data.blocks.get(block_path.block_index as usize))
     }
+
+    /// Median age in days of the unspent supply, walks every block so it's only meant to be called once per date
+    pub fn compute_median_supply_age(&self, supply: Amount, timestamp: Timestamp) -> f32 {
+        let supply = supply.to_sat();
+
+        if supply == 0 {
+            return 0.0;
+        }
+
+        // Blocks are sorted from the oldest to the youngest
+        let mut cumulated = 0;
+
+        self.iter()
+            .flat_map(|date_data| &date_data.blocks)
+            .find(|block_data| {
+                cumulated += block_data.amount.to_sat();
+                cumulated * 2 >= supply
+            })
+            .map_or(0.0, |block_data| {
+                (timestamp
+                    .checked_sub(*block_data.timestamp)
+                    .unwrap_or_default() as f64
+                    / ONE_DAY_IN_S as f64) as f32
+            })
+    }
 }
 
 impl AnyState for DateDataVec {
@@ -4,6 +4,7 @@ mod _trait;
 mod cohorts_states;
 mod counters;
 mod date_data_vec;
+mod supply_totals;
 
 pub use _trait::*;
 
@@ -11,6 +12,7 @@ use allocative::Allocative;
 pub use cohorts_states::*;
 use counters::*;
 use date_data_vec::*;
+pub use supply_totals::*;
 
 use crate::utils::log;
 
@@ -18,6 +20,7 @@ use crate::utils::log;
 pub struct States {
     pub address_counters: Counters,
     pub date_data_vec: DateDataVec,
+    pub supply_totals: SupplyTotals,
     pub address_cohorts_durable_states: Option<AddressCohortsDurableStates>,
     pub utxo_cohorts_durable_states: Option<UTXOCohortsDurableStates>,
 }
@@ -30,10 +33,13 @@ impl States {
 
         let date_data_vec = date_data_vec_handle.join().unwrap()?;
 
+        let supply_totals = SupplyTotals::init(&date_data_vec);
+
         Ok(Self {
             address_cohorts_durable_states: None,
             address_counters,
             date_data_vec,
+            supply_totals,
             utxo_cohorts_durable_states: None,
         })
     }
@@ -43,6 +49,8 @@ impl States {
 
         let _ = self.date_data_vec.reset();
 
+        self.supply_totals = SupplyTotals::default();
+
         self.utxo_cohorts_durable_states = None;
 
         if include_addresses {
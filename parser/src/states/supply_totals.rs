@@ -0,0 +1,149 @@
+use std::collections::BTreeMap;
+
+use allocative::Allocative;
+
+use crate::{
+    structs::{Amount, BlockData, BlockPath, Epoch, Height, Timestamp},
+    utils::{ONE_DAY_IN_S, ONE_YEAR_IN_DAYS},
+};
+
+use super::DateDataVec;
+
+/// Last block of the Satoshi era, every block up to it counts whoever mined it
+const SATOSHI_ERA_LAST_HEIGHT: u32 = 54_316;
+
+///
+/// Running totals of the unspent supply, updated with what each block receives and sends instead of walking every block.
+///
+/// Not exported, rebuilt from the date data vec on import.
+///
+#[derive(Default, Debug, Allocative)]
+pub struct SupplyTotals {
+    /// In sats
+    supply: u64,
+    /// Sum of the unspent amounts (in sats) multiplied by the timestamp of their block
+    supply_times_timestamp: u128,
+    /// In sats, of the blocks up to `SATOSHI_ERA_LAST_HEIGHT`
+    satoshi_era_supply: u64,
+    /// In sats, of the blocks at least 10 years old by epoch
+    from_10y_supply_by_epoch: BTreeMap<u16, u64>,
+    /// First block that isn't 10 years old yet
+    from_10y_cursor: BlockPath,
+    /// Height of `from_10y_cursor`, all blocks below are 10 years old
+    from_10y_end: Height,
+}
+
+impl SupplyTotals {
+    pub fn init(date_data_vec: &DateDataVec) -> Self {
+        let mut s = Self::default();
+
+        date_data_vec
+            .iter()
+            .flat_map(|date_data| &date_data.blocks)
+            .for_each(|block_data| s.receive(block_data, block_data.amount));
+
+        if let Some(last_block_data) = date_data_vec.last_block() {
+            s.age(date_data_vec, last_block_data.timestamp);
+        }
+
+        s
+    }
+
+    pub fn receive(&mut self, block_data: &BlockData, amount: Amount) {
+        let sats = amount.to_sat();
+
+        self.supply += sats;
+        self.supply_times_timestamp += sats as u128 * *block_data.timestamp as u128;
+
+        if *block_data.height <= SATOSHI_ERA_LAST_HEIGHT {
+            self.satoshi_era_supply += sats;
+        }
+
+        if block_data.height < self.from_10y_end {
+            *self
+                .from_10y_supply_by_epoch
+                .entry(Epoch::from(&block_data.height).0)
+                .or_default() += sats;
+        }
+    }
+
+    pub fn send(&mut self, block_data: &BlockData, amount: Amount) {
+        let sats = amount.to_sat();
+
+        self.supply -= sats;
+        self.supply_times_timestamp -= sats as u128 * *block_data.timestamp as u128;
+
+        if *block_data.height <= SATOSHI_ERA_LAST_HEIGHT {
+            self.satoshi_era_supply -= sats;
+        }
+
+        if block_data.height < self.from_10y_end {
+            *self
+                .from_10y_supply_by_epoch
+                .get_mut(&Epoch::from(&block_data.height).0)
+                .unwrap() -= sats;
+        }
+    }
+
+    /// Moves the blocks that became 10 years old at `timestamp` into their epoch total
+    pub fn age(&mut self, date_data_vec: &DateDataVec, timestamp: Timestamp) {
+        while let Some(date_data) = date_data_vec.get_date_data(&self.from_10y_cursor) {
+            let Some(block_data) = date_data
+                .blocks
+                .get(self.from_10y_cursor.block_index as usize)
+            else {
+                if self.from_10y_cursor.date_index as usize + 1 >= date_data_vec.len() {
+                    break;
+                }
+
+                self.from_10y_cursor = BlockPath::new(self.from_10y_cursor.date_index + 1, 0);
+
+                continue;
+            };
+
+            if Timestamp::difference_in_days_between(block_data.timestamp, timestamp)
+                < 10 * ONE_YEAR_IN_DAYS as u32
+            {
+                break;
+            }
+
+            *self
+                .from_10y_supply_by_epoch
+                .entry(Epoch::from(&block_data.height).0)
+                .or_default() += block_data.amount.to_sat();
+
+            self.from_10y_end = block_data.height + 1_u32;
+            self.from_10y_cursor.block_index += 1;
+        }
+    }
+
+    pub fn supply(&self) -> Amount {
+        Amount::from_sat(self.supply)
+    }
+
+    /// Unspent supply of every output of all blocks up to height 54,316, not only the ones attributed to Satoshi
+    pub fn satoshi_era_supply(&self) -> Amount {
+        Amount::from_sat(self.satoshi_era_supply)
+    }
+
+    /// Unspent supply of the blocks of the epoch that are at least 10 years old
+    pub fn supply_from_10y(&self, epoch: &Epoch) -> Amount {
+        Amount::from_sat(
+            self.from_10y_supply_by_epoch
+                .get(&epoch.0)
+                .cloned()
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Average age in days of the unspent supply, weighted by amount
+    pub fn average_supply_age(&self, timestamp: Timestamp) -> f32 {
+        if self.supply == 0 {
+            return 0.0;
+        }
+
+        let average_timestamp = self.supply_times_timestamp as f64 / self.supply as f64;
+
+        ((*timestamp as f64 - average_timestamp).max(0.0) / ONE_DAY_IN_S as f64) as f32
+    }
+}
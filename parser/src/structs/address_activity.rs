@@ -0,0 +1,63 @@
+use super::{AddressData, AddressRealizedData, Amount};
+
+/// Number of addresses by kind of activity in a block
+#[derive(Default, Debug)]
+pub struct AddressActivity {
+    pub sending: u32,
+    pub receiving: u32,
+    pub active: u32,
+    /// Receiving addresses that had already received before
+    pub reused: u32,
+    /// Addresses that were empty and received again
+    pub revived: u32,
+    /// Addresses that sent all their coins
+    pub emptied: u32,
+    /// Indexes of the sending addresses, to count them once per day
+    pub sending_indexes: Vec<u32>,
+    /// Indexes of the receiving addresses, to count them once per day
+    pub receiving_indexes: Vec<u32>,
+    /// Indexes of the reused addresses, to count them once per day
+    pub reused_indexes: Vec<u32>,
+}
+
+impl AddressActivity {
+    pub fn iterate(
+        &mut self,
+        address_index: u32,
+        address_realized_data: &AddressRealizedData,
+        current_address_data: &AddressData,
+    ) {
+        let initial_address_data = &address_realized_data.initial_address_data;
+
+        let has_sent = address_realized_data.utxos_destroyed > 0;
+        let has_received = address_realized_data.utxos_created > 0;
+        let had_received = initial_address_data.received > Amount::ZERO;
+
+        if has_sent {
+            self.sending += 1;
+            self.sending_indexes.push(address_index);
+        }
+
+        if has_received {
+            self.receiving += 1;
+            self.receiving_indexes.push(address_index);
+        }
+
+        if has_sent || has_received {
+            self.active += 1;
+        }
+
+        if has_received && had_received {
+            self.reused += 1;
+            self.reused_indexes.push(address_index);
+
+            if initial_address_data.is_empty() {
+                self.revived += 1;
+            }
+        }
+
+        if !initial_address_data.is_empty() && current_address_data.is_empty() {
+            self.emptied += 1;
+        }
+    }
+}
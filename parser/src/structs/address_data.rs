@@ -1,10 +1,13 @@
 use allocative::Allocative;
+use bincode::{Decode, Encode};
 use color_eyre::eyre::eyre;
 use sanakirja::{direct_repr, Storable, UnsizedStorable};
 
 use super::{AddressType, Amount, EmptyAddressData, LiquidityClassification, Price};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Allocative)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default, Encode, Decode, Allocative,
+)]
 pub struct AddressData {
     pub address_type: AddressType,
     pub amount: Amount,
@@ -92,6 +95,12 @@ impl AddressData {
         }
     }
 
+    /// Hashed addresses expose their public key once they have spent, P2PK and P2TR ones always do
+    pub fn has_revealed_pubkey(&self) -> bool {
+        matches!(self.address_type, AddressType::P2PKH | AddressType::P2WPKH)
+            && self.sent > Amount::ZERO
+    }
+
     pub fn compute_liquidity_classification(&self) -> LiquidityClassification {
         LiquidityClassification::new(self.sent, self.received)
     }
@@ -9,4 +9,6 @@ pub enum AddressSplit {
     Type(AddressType),
     Size(AddressSize),
     Liquidity(AddressLiquidity),
+    /// P2PKH and P2WPKH addresses still holding coins after having spent
+    RevealedPubkey,
 }
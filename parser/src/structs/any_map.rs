@@ -1,8 +1,11 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
 
 use serde_json::Value;
 
-use super::MapKind;
+use super::{Date, MapKind};
 
 pub trait AnyMap {
     fn path(&self) -> &Path;
@@ -25,8 +28,15 @@ pub trait AnyMap {
     fn pre_export(&mut self);
     fn export(&self) -> color_eyre::Result<()>;
     fn post_export(&mut self);
+    /// Drops every imported chunk but the latest one, needs to be called after `post_export`
+    fn evict(&mut self);
 
     fn delete_files(&self);
 
     fn kind(&self) -> MapKind;
+
+    /// Reads every exported chunk and returns the problems found: decode failures, version mismatches, missing keys and NaN/inf values of inserted maps
+    ///
+    /// Missing dates are fine when no block was mined during them
+    fn verify(&self, dates_without_blocks: &BTreeSet<Date>) -> Vec<String>;
 }
@@ -1,11 +1,24 @@
 use std::fmt::Debug;
 
 use allocative::Allocative;
+use bincode::{Decode, Encode};
 use derive_deref::{Deref, DerefMut};
 use sanakirja::{direct_repr, Storable, UnsizedStorable};
 
 #[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deref, DerefMut, Default, Copy, Allocative,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Deref,
+    DerefMut,
+    Default,
+    Copy,
+    Encode,
+    Decode,
+    Allocative,
 )]
 pub struct U8x19([u8; 19]);
 direct_repr!(U8x19);
@@ -18,7 +31,19 @@ impl From<&[u8]> for U8x19 {
 }
 
 #[derive(
-    Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Deref, DerefMut, Default, Copy, Allocative,
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Deref,
+    DerefMut,
+    Default,
+    Copy,
+    Encode,
+    Decode,
+    Allocative,
 )]
 pub struct U8x31([u8; 31]);
 direct_repr!(U8x31);
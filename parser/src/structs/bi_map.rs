@@ -11,7 +11,7 @@ use super::{
     AnyDateMap, AnyHeightMap, AnyMap, Date, DateMap, Height, HeightMap, MapKind, MapValue,
 };
 
-#[derive(Allocative)]
+#[derive(Clone, Allocative)]
 pub struct BiMap<Value>
 where
     Value: MapValue,
@@ -215,6 +215,149 @@ where
             .multi_insert_simple_average(dates, &mut source.date, days);
     }
 
+    #[allow(unused)]
+    pub fn multi_insert_exponential_average<K>(
+        &mut self,
+        heights: &[Height],
+        dates: &[Date],
+        source: &mut BiMap<K>,
+        days: usize,
+    ) where
+        K: MapValue,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<K> + LossyFrom<Value>,
+    {
+        self.height.multi_insert_exponential_average(
+            heights,
+            &mut source.height,
+            TARGET_BLOCKS_PER_DAY * days,
+        );
+        self.date
+            .multi_insert_exponential_average(dates, &mut source.date, days);
+    }
+
+    #[allow(unused)]
+    pub fn multi_insert_standard_deviation<K>(
+        &mut self,
+        heights: &[Height],
+        dates: &[Date],
+        source: &mut BiMap<K>,
+        days: Option<usize>,
+    ) where
+        K: MapValue,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<K>,
+    {
+        self.height.multi_insert_standard_deviation(
+            heights,
+            &mut source.height,
+            days.map(|days| TARGET_BLOCKS_PER_DAY * days),
+        );
+        self.date
+            .multi_insert_standard_deviation(dates, &mut source.date, days);
+    }
+
+    #[allow(unused)]
+    pub fn multi_insert_z_score<K>(
+        &mut self,
+        heights: &[Height],
+        dates: &[Date],
+        source: &mut BiMap<K>,
+        average: &mut BiMap<Value>,
+        standard_deviation: &mut BiMap<Value>,
+    ) where
+        K: MapValue,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<K> + LossyFrom<Value>,
+    {
+        self.height.multi_insert_z_score(
+            heights,
+            &mut source.height,
+            &mut average.height,
+            &mut standard_deviation.height,
+        );
+        self.date.multi_insert_z_score(
+            dates,
+            &mut source.date,
+            &mut average.date,
+            &mut standard_deviation.date,
+        );
+    }
+
+    #[allow(unused)]
+    pub fn multi_insert_bollinger_band(
+        &mut self,
+        heights: &[Height],
+        dates: &[Date],
+        average: &mut BiMap<Value>,
+        standard_deviation: &mut BiMap<Value>,
+        multiplier: f64,
+    ) where
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<Value>,
+    {
+        self.height.multi_insert_bollinger_band(
+            heights,
+            &mut average.height,
+            &mut standard_deviation.height,
+            multiplier,
+        );
+        self.date.multi_insert_bollinger_band(
+            dates,
+            &mut average.date,
+            &mut standard_deviation.date,
+            multiplier,
+        );
+    }
+
+    #[allow(unused)]
+    pub fn multi_insert_correlation<A, B>(
+        &mut self,
+        heights: &[Height],
+        dates: &[Date],
+        a: &mut BiMap<A>,
+        b: &mut BiMap<B>,
+        days: usize,
+    ) where
+        A: MapValue,
+        B: MapValue,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<A> + LossyFrom<B>,
+    {
+        self.height.multi_insert_correlation(
+            heights,
+            &mut a.height,
+            &mut b.height,
+            TARGET_BLOCKS_PER_DAY * days,
+        );
+        self.date
+            .multi_insert_correlation(dates, &mut a.date, &mut b.date, days);
+    }
+
+    #[allow(unused)]
+    pub fn multi_insert_beta<A, B>(
+        &mut self,
+        heights: &[Height],
+        dates: &[Date],
+        a: &mut BiMap<A>,
+        b: &mut BiMap<B>,
+        days: usize,
+    ) where
+        A: MapValue,
+        B: MapValue,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<A> + LossyFrom<B>,
+    {
+        self.height.multi_insert_beta(
+            heights,
+            &mut a.height,
+            &mut b.height,
+            TARGET_BLOCKS_PER_DAY * days,
+        );
+        self.date
+            .multi_insert_beta(dates, &mut a.date, &mut b.date, days);
+    }
+
     pub fn multi_insert_net_change(
         &mut self,
         heights: &[Height],
@@ -1,7 +1,9 @@
 use allocative::Allocative;
 use bincode::{Decode, Encode};
 
-#[derive(Debug, Clone, PartialEq, PartialOrd, Eq, Ord, Copy, Encode, Decode, Allocative)]
+#[derive(
+    Debug, Default, Clone, PartialEq, PartialOrd, Eq, Ord, Copy, Encode, Decode, Allocative,
+)]
 pub struct BlockPath {
     pub date_index: u16,
     pub block_index: u16,
@@ -4,7 +4,7 @@ use std::{
 };
 
 use biter::bitcoincore_rpc::Auth;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use color_eyre::eyre::eyre;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +13,10 @@ use crate::log;
 #[derive(Parser, Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 #[command(version, about, long_about = None)]
 pub struct Config {
+    #[command(subcommand)]
+    #[serde(skip)]
+    pub command: Option<Command>,
+
     /// Bitcoin data directory path, saved
     #[arg(long, value_name = "DIR")]
     pub datadir: Option<String>,
@@ -41,9 +45,14 @@ pub struct Config {
     #[arg(long, value_name = "SECONDS")]
     pub delay: Option<u64>,
 
-    // Maximum ram you want the program to use in GB, default: 50% of total, not saved
-    // #[arg(long, value_name = "GB")]
-    // pub max_ram: Option<f64>,
+    /// Compact fragmented databases one at a time in the background to reduce their footprint, default: false, saved
+    #[arg(long, value_name = "BOOL")]
+    pub background_compaction: Option<bool>,
+
+    /// Maximum ram you want the program to use in GB, exports earlier and drops caches when reached, default: unlimited, saved
+    #[arg(long, value_name = "GB")]
+    pub max_ram: Option<f64>,
+
     /// Start a dry run, default: false, not saved
     #[arg(long, value_name = "BOOL")]
     dry_run: Option<bool>,
@@ -52,15 +61,51 @@ pub struct Config {
     #[arg(long, value_name = "BOOL")]
     record_ram_usage: Option<bool>,
 
+    /// Record the changes made to the databases at each export to benchmark backends with `bench_backends`, default: false, not saved
+    #[arg(long, value_name = "BOOL")]
+    record_databases: Option<bool>,
+
     /// Recompute all computed datasets, default: false, not saved
     #[arg(long, value_name = "BOOL")]
     recompute_computed: Option<bool>,
 
+    /// Recompute the computed datasets whose path matches the glob and the ones computed from them (needs a dependency graph saved by a previous export), example: 'price/*_sma_ratio*', default: none, not saved
+    #[arg(long, value_name = "GLOB")]
+    recompute: Option<String>,
+
     /// Start the program by defragmenting all databases to reduce their footprint, default: false, not saved
     #[arg(long, value_name = "BOOL")]
     first_defragment: Option<bool>,
 }
 
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Check that the exported datasets are complete and consistent, exits with 1 if they're not
+    Verify,
+    /// Create or restore a snapshot of the datasets, databases and states to bootstrap another instance
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+}
+
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum SnapshotAction {
+    /// Archive everything needed to resume parsing from the last safe export, the parser must be stopped
+    Create {
+        /// Path of the archive
+        path: PathBuf,
+        /// Height expected to be snapshotted, fails if it isn't the one of the last export since older states aren't kept
+        #[arg(long)]
+        height: Option<u32>,
+    },
+    /// Check a snapshot against the node and extract it, then resume parsing from there
+    Restore {
+        /// Path of the archive
+        path: PathBuf,
+    },
+}
+
 impl Config {
     const PATH: &'static str = "./config.toml";
 
@@ -100,25 +145,37 @@ impl Config {
             config_saved.delay = Some(delay);
         }
 
-        // if let Some(max_ram) = config_args.max_ram.take() {
-        //     config_saved.max_ram = Some(max_ram);
-        // }
+        if let Some(background_compaction) = config_args.background_compaction.take() {
+            config_saved.background_compaction = Some(background_compaction);
+        }
+
+        if let Some(max_ram) = config_args.max_ram.take() {
+            config_saved.max_ram = Some(max_ram);
+        }
 
         // Done importing
 
         let mut config = config_saved;
 
-        config.check();
+        config.command = config_args.command.take();
+
+        // Verifying only reads the datasets, no need for a node
+        if config.command != Some(Command::Verify) {
+            config.check();
+        }
 
         config.write()?;
 
         config.dry_run = config_args.dry_run.take();
         config.record_ram_usage = config_args.record_ram_usage.take();
+        config.record_databases = config_args.record_databases.take();
         config.recompute_computed = config_args.recompute_computed.take();
+        config.recompute = config_args.recompute.take();
         config.first_defragment = config_args.first_defragment.take();
 
         log("---");
         log("Configuration:");
+        log(&format!("command: {:?}", config.command));
         log(&format!("datadir: {:?}", config.datadir));
         log(&format!("rpcconnect: {:?}", config.rpcconnect));
         log(&format!("rpcport: {:?}", config.rpcport));
@@ -126,13 +183,19 @@ impl Config {
         log(&format!("rpcuser: {:?}", config.rpcuser));
         log(&format!("rpcpassword: {:?}", config.rpcpassword));
         log(&format!("delay: {:?}", config.delay));
-        // log(&format!("max_ram: {:?}", config.max_ram));
+        log(&format!(
+            "background_compaction: {:?}",
+            config.background_compaction
+        ));
+        log(&format!("max_ram: {:?}", config.max_ram));
         log(&format!("dry_run: {:?}", config.dry_run));
         log(&format!("record_ram_usage: {:?}", config.record_ram_usage));
+        log(&format!("record_databases: {:?}", config.record_databases));
         log(&format!(
             "recompute_computed: {:?}",
             config.recompute_computed
         ));
+        log(&format!("recompute: {:?}", config.recompute));
         log(&format!("first_defragment: {:?}", config.first_defragment));
         log("---");
 
@@ -201,10 +264,26 @@ impl Config {
         self.record_ram_usage.is_some_and(|b| b)
     }
 
+    pub fn record_databases(&self) -> bool {
+        self.record_databases.is_some_and(|b| b)
+    }
+
     pub fn recompute_computed(&self) -> bool {
         self.recompute_computed.is_some_and(|b| b)
     }
 
+    pub fn recompute(&self) -> Option<&str> {
+        self.recompute.as_deref()
+    }
+
+    pub fn disable_recompute(&mut self) {
+        self.recompute.take();
+    }
+
+    pub fn background_compaction(&self) -> bool {
+        self.background_compaction.unwrap_or(false)
+    }
+
     pub fn first_defragment(&self) -> bool {
         self.first_defragment.is_some_and(|b| b)
     }
@@ -13,7 +13,7 @@ use serde::{Deserialize, Serialize};
 
 use crate::utils::ONE_DAY_IN_S;
 
-use super::{DateMapChunkId, MapKey, Timestamp};
+use super::{DateMapChunkId, MapChunkId, MapKey, Timestamp};
 
 const NUMBER_OF_UNSAFE_DATES: usize = 2;
 const MIN_YEAR: i32 = 2009;
@@ -40,6 +40,10 @@ impl Date {
         Self(date)
     }
 
+    pub fn genesis() -> Self {
+        Self(NaiveDate::from_ymd_opt(2009, 1, 3).unwrap())
+    }
+
     pub fn today() -> Self {
         Self(chrono::offset::Utc::now().date_naive())
     }
@@ -92,6 +96,12 @@ impl MapKey<DateMapChunkId> for Date {
         DateMapChunkId::new(self)
     }
 
+    fn from_chunk_id(chunk_id: &DateMapChunkId) -> Self {
+        let year = chunk_id.to_usize() as i32;
+
+        Self::wrap(NaiveDate::from_ymd_opt(year, 1, 1).unwrap()).max(Self::genesis())
+    }
+
     fn to_first_unsafe(&self) -> Option<Self> {
         let offset = NUMBER_OF_UNSAFE_DATES - 1;
 
@@ -130,9 +140,26 @@ impl MapKey<DateMapChunkId> for Date {
         self.iter_days().take_while(|d| d <= other).map(Date::wrap)
     }
 
+    fn to_date(&self) -> Option<Date> {
+        Some(*self)
+    }
+
     fn map_name<'a>() -> &'a str {
         "date"
     }
+
+    /// Number of days since genesis
+    fn to_usize(&self) -> usize {
+        self.difference_in_days_between(Self::genesis()) as usize
+    }
+
+    fn from_usize(days: usize) -> Self {
+        Self::wrap(
+            Self::genesis()
+                .checked_add_days(Days::new(days as u64))
+                .unwrap(),
+        )
+    }
 }
 
 impl fmt::Display for Date {
@@ -41,4 +41,8 @@ impl MapChunkId for DateMapChunkId {
     fn from_usize(id: usize) -> Self {
         Self(id as i32)
     }
+
+    fn next(self) -> Self {
+        Self(self.0 + 1)
+    }
 }
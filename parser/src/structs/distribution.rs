@@ -0,0 +1,89 @@
+use allocative::Allocative;
+use bincode::{Decode, Encode};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Amount, MapValue, Price};
+
+///
+/// Supply in BTC by price paid, in bins of the same width in log scale.
+///
+/// Fixed size to be a `MapValue`, prices under the first bin are counted in it and prices over the last one in it too.
+///
+#[derive(Debug, Clone, Copy, Encode, Decode, Allocative)]
+pub struct Distribution([f32; Distribution::LEN]);
+
+impl Distribution {
+    /// 0.01$
+    const MIN_EXPONENT: i32 = -2;
+    const BINS_PER_DECADE: usize = 20;
+    const DECADES: usize = 9;
+    pub const LEN: usize = Self::BINS_PER_DECADE * Self::DECADES;
+
+    pub fn bin(price: Price) -> usize {
+        let dollars = price.to_dollar();
+
+        if dollars <= 0.0 {
+            return 0;
+        }
+
+        let bin =
+            ((dollars.log10() - Self::MIN_EXPONENT as f64) * Self::BINS_PER_DECADE as f64).floor();
+
+        bin.clamp(0.0, (Self::LEN - 1) as f64) as usize
+    }
+
+    /// Lowest price of the bin in dollars
+    pub fn bin_start(bin: usize) -> f64 {
+        10_f64.powf(Self::MIN_EXPONENT as f64 + bin as f64 / Self::BINS_PER_DECADE as f64)
+    }
+
+    pub fn increment(&mut self, price: Price, amount: Amount) {
+        self.0[Self::bin(price)] += amount.to_btc() as f32;
+    }
+
+    /// Non empty bins as (lowest price, highest price, supply)
+    pub fn bins(&self) -> Vec<(f64, f64, f32)> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, supply)| **supply > 0.0)
+            .map(|(bin, supply)| (Self::bin_start(bin), Self::bin_start(bin + 1), *supply))
+            .collect()
+    }
+}
+
+impl Default for Distribution {
+    fn default() -> Self {
+        Self([0.0; Self::LEN])
+    }
+}
+
+impl Serialize for Distribution {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.as_slice().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Distribution {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let bins = Vec::<f32>::deserialize(deserializer)?;
+
+        let len = bins.len();
+
+        bins.try_into()
+            .map(Self)
+            .map_err(|_| de::Error::invalid_length(len, &"Distribution::LEN bins"))
+    }
+}
+
+impl MapValue for Distribution {
+    fn is_finite(&self) -> bool {
+        self.0.iter().all(|supply| supply.is_finite())
+    }
+}
@@ -1,9 +1,12 @@
 use allocative::Allocative;
+use bincode::{Decode, Encode};
 use sanakirja::{direct_repr, Storable, UnsizedStorable};
 
 use super::{AddressData, AddressType, Amount};
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Allocative)]
+#[derive(
+    Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, Encode, Decode, Allocative,
+)]
 pub struct EmptyAddressData(u64);
 direct_repr!(EmptyAddressData);
 
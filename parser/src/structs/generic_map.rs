@@ -1,11 +1,15 @@
 use std::{
-    collections::{BTreeMap, VecDeque},
+    collections::{BTreeMap, BTreeSet, VecDeque},
     fmt::Debug,
     fs,
     iter::Sum,
     mem,
     ops::{Add, ControlFlow, Div, Mul, Sub},
     path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 use allocative::Allocative;
@@ -15,12 +19,22 @@ use ordered_float::OrderedFloat;
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
+    io::{Columnar, COLUMNAR_FILE_NAME},
     log,
     utils::{get_percentile, LossyFrom},
     Serialization,
 };
 
-use super::{AnyMap, MapValue};
+use super::{AnyMap, Date, MapDependencies, MapValue};
+
+/// When set, outdated chunks are kept on import instead of being deleted, used by `verify` which only reports them
+pub static READ_ONLY: AtomicBool = AtomicBool::new(false);
+
+// Serialization of the maps created with `new_bin`
+#[cfg(not(feature = "columnar"))]
+const BIN_SERIALIZATION: Serialization = Serialization::Binary;
+#[cfg(feature = "columnar")]
+const BIN_SERIALIZATION: Serialization = Serialization::Columnar;
 
 #[derive(Debug, Clone, Copy, Allocative, PartialEq, Eq)]
 pub enum MapKind {
@@ -34,6 +48,8 @@ where
     ChunkId: MapChunkId,
 {
     fn to_chunk_id(&self) -> ChunkId;
+    /// First key of the chunk
+    fn from_chunk_id(chunk_id: &ChunkId) -> Self;
     fn to_first_unsafe(&self) -> Option<Self>;
     fn to_serialized_key(&self) -> Self;
     fn is_out_of_bounds(&self) -> bool;
@@ -43,6 +59,9 @@ where
     fn iter_up_to(&self, other: &Self) -> impl Iterator<Item = Self>;
     fn map_name<'a>() -> &'a str;
 
+    fn to_date(&self) -> Option<Date> {
+        None
+    }
     fn from_usize(_: usize) -> Self {
         unreachable!()
     }
@@ -61,6 +80,8 @@ where
     fn version(&self) -> u32;
     fn get(&self, serialized_key: &Key) -> Option<&Value>;
     fn last(&self) -> Option<&Value>;
+    /// Unserialized keys
+    fn entries(&self, chunk_id: &ChunkId) -> Vec<(Key, Value)>;
     fn extend(&mut self, map: BTreeMap<Key, Value>);
 }
 
@@ -72,9 +93,11 @@ where
     fn from_path(path: &Path) -> color_eyre::Result<Self>;
     fn to_usize(self) -> usize;
     fn from_usize(id: usize) -> Self;
+    /// Id of the following chunk
+    fn next(self) -> Self;
 }
 
-#[derive(Debug, Allocative)]
+#[derive(Debug, Clone, Allocative)]
 pub struct GenericMap<Key, Value, ChunkId, Serialized> {
     version: u32,
     kind: MapKind,
@@ -91,6 +114,13 @@ pub struct GenericMap<Key, Value, ChunkId, Serialized> {
 
     imported: BTreeMap<ChunkId, Serialized>,
     to_insert: BTreeMap<ChunkId, BTreeMap<Key, Value>>,
+
+    id: usize,
+    sources: BTreeSet<usize>,
+
+    /// Opened on the first read of a value whose chunk isn't imported, only with `Serialization::Columnar`
+    #[allocative(skip)]
+    columnar: Option<Arc<Columnar>>,
 }
 
 impl<Key, Value, ChunkId, Serialized> GenericMap<Key, Value, ChunkId, Serialized>
@@ -101,11 +131,11 @@ where
     Serialized: MapSerialized<Key, Value, ChunkId>,
 {
     pub fn new_bin(version: u32, kind: MapKind, path: &str) -> Self {
-        Self::new(version, kind, path, Serialization::Binary, 1, true)
+        Self::new(version, kind, path, BIN_SERIALIZATION, 1, true)
     }
 
     pub fn _new_bin(version: u32, kind: MapKind, path: &str, export_last: bool) -> Self {
-        Self::new(version, kind, path, Serialization::Binary, 1, export_last)
+        Self::new(version, kind, path, BIN_SERIALIZATION, 1, export_last)
     }
 
     pub fn new_json(version: u32, kind: MapKind, path: &str, export_last: bool) -> Self {
@@ -145,6 +175,8 @@ where
             }
         };
 
+        let id = MapDependencies::register(&path_all);
+
         let mut s = Self {
             version,
             kind,
@@ -161,17 +193,28 @@ where
 
             to_insert: BTreeMap::default(),
             imported: BTreeMap::default(),
+
+            id,
+            sources: BTreeSet::default(),
+
+            columnar: None,
         };
 
+        if kind == MapKind::Computed && MapDependencies::should_recompute(&s.path_all) {
+            log(&format!("Recomputing: {path}/{}", Key::map_name()));
+
+            s.delete_files();
+        }
+
         s.read_dir()
             .into_iter()
             .rev()
             .take(chunks_in_memory)
             .for_each(|(chunk_start, path)| {
-                if let Ok(serialized) = s.import(&path) {
+                if let Ok(serialized) = s.import_chunk(&chunk_start, &path) {
                     if serialized.version() == s.version {
                         s.imported.insert(chunk_start, serialized);
-                    } else {
+                    } else if !READ_ONLY.load(Ordering::SeqCst) {
                         s.delete_files();
                     }
                 }
@@ -199,6 +242,31 @@ where
     }
 
     pub fn _read_dir(path: &Path, serialization: &Serialization) -> BTreeMap<ChunkId, PathBuf> {
+        if *serialization == Serialization::Columnar {
+            // Every chunk is in the same file
+            let path = path.join(COLUMNAR_FILE_NAME);
+
+            let Some(last_index) = Columnar::open(&path)
+                .ok()
+                .and_then(|columnar| columnar.len().checked_sub(1))
+            else {
+                return BTreeMap::default();
+            };
+
+            let last_chunk_id = Key::from_usize(last_index).to_chunk_id();
+
+            let mut chunk_id = Key::from_usize(0).to_chunk_id();
+
+            let mut chunks = BTreeMap::from([(chunk_id, path.clone())]);
+
+            while chunk_id < last_chunk_id {
+                chunk_id = chunk_id.next();
+                chunks.insert(chunk_id, path.clone());
+            }
+
+            return chunks;
+        }
+
         fs::read_dir(path)
             .unwrap()
             .map(|entry| entry.unwrap().path())
@@ -213,8 +281,92 @@ where
             .collect()
     }
 
-    fn import(&self, path: &Path) -> color_eyre::Result<Serialized> {
-        self.serialization.import::<Serialized>(path)
+    fn import_chunk(&self, chunk_id: &ChunkId, path: &Path) -> color_eyre::Result<Serialized> {
+        Self::_import_chunk(chunk_id, path, &self.serialization)
+    }
+
+    pub fn _import_chunk(
+        chunk_id: &ChunkId,
+        path: &Path,
+        serialization: &Serialization,
+    ) -> color_eyre::Result<Serialized> {
+        if *serialization != Serialization::Columnar {
+            return serialization.import::<Serialized>(path);
+        }
+
+        let columnar = Columnar::open(path)?;
+
+        let first_index = Key::from_chunk_id(chunk_id).to_usize();
+
+        let keys = (first_index..columnar.len())
+            .map(Key::from_usize)
+            .take_while(|key| key.to_chunk_id() == *chunk_id)
+            .collect_vec();
+
+        let values = columnar.range::<Value>(first_index..first_index + keys.len())?;
+
+        let mut serialized = Serialized::new(columnar.version());
+
+        serialized.extend(
+            keys.iter()
+                .zip(values)
+                .filter_map(|(key, value)| Some((key.to_serialized_key(), value?)))
+                .collect(),
+        );
+
+        Ok(serialized)
+    }
+
+    /// Value of a single key, read straight from the file with `Serialization::Columnar` and by importing its chunk otherwise
+    pub fn _import_key(
+        path: &Path,
+        serialization: &Serialization,
+        key: &Key,
+    ) -> color_eyre::Result<Option<Value>> {
+        if *serialization == Serialization::Columnar {
+            return Columnar::open(&path.join(COLUMNAR_FILE_NAME))?.get(key.to_usize());
+        }
+
+        let chunk_id = key.to_chunk_id();
+
+        let Some(path) = Self::_read_dir(path, serialization).remove(&chunk_id) else {
+            return Ok(None);
+        };
+
+        Ok(Self::_import_chunk(&chunk_id, &path, serialization)?
+            .get(&key.to_serialized_key())
+            .cloned())
+    }
+
+    /// Last key and its value, read straight from the file with `Serialization::Columnar` and by importing the last chunk otherwise
+    pub fn _import_last(
+        path: &Path,
+        serialization: &Serialization,
+    ) -> color_eyre::Result<Option<(Key, Value)>> {
+        if *serialization == Serialization::Columnar {
+            return Ok(Columnar::open(&path.join(COLUMNAR_FILE_NAME))?
+                .last()?
+                .map(|(index, value)| (Key::from_usize(index), value)));
+        }
+
+        let Some((chunk_id, path)) = Self::_read_dir(path, serialization).pop_last() else {
+            return Ok(None);
+        };
+
+        Ok(Self::_import_chunk(&chunk_id, &path, serialization)?
+            .entries(&chunk_id)
+            .pop())
+    }
+
+    /// Every exported value, chunks that can't be decoded are skipped
+    pub fn import_all_entries(&self) -> BTreeMap<Key, Value> {
+        self.read_dir()
+            .into_iter()
+            .filter_map(|(chunk_id, path)| {
+                Some((chunk_id, self.import_chunk(&chunk_id, &path).ok()?))
+            })
+            .flat_map(|(chunk_id, serialized)| serialized.entries(&chunk_id))
+            .collect()
     }
 
     pub fn insert(&mut self, key: Key, value: Value) -> Value {
@@ -231,6 +383,11 @@ where
             panic!("Called at the wrong place");
         }
 
+        match kind {
+            MapKind::Computed => MapDependencies::record_insert(self.id, &mut self.sources),
+            MapKind::Inserted => MapDependencies::clear_reads(),
+        }
+
         if !self.is_key_safe(key) {
             self.to_insert
                 .entry(key.to_chunk_id())
@@ -270,6 +427,8 @@ where
     }
 
     pub fn get_or_import(&mut self, key: &Key) -> Option<Value> {
+        MapDependencies::record_read(self.id);
+
         if key.is_out_of_bounds() {
             return None;
         }
@@ -282,12 +441,18 @@ where
             .get(&chunk_id)
             .and_then(|tree| tree.get(&serialized_key).cloned())
             .or_else(|| {
+                if self.serialization == Serialization::Columnar
+                    && !self.imported.contains_key(&chunk_id)
+                {
+                    return self.get_columnar(key);
+                }
+
                 #[allow(clippy::map_entry)] // Can't be mut and then use read_dir()
                 if !self.imported.contains_key(&chunk_id) {
                     let dir_content = self.read_dir();
 
                     if let Some(path) = dir_content.get(&chunk_id) {
-                        let serialized = self.import(path).unwrap();
+                        let serialized = self.import_chunk(&chunk_id, path).unwrap();
 
                         self.imported.insert(chunk_id, serialized);
                     }
@@ -299,6 +464,49 @@ where
                     .cloned()
             })
     }
+
+    /// Reads a single value without importing its chunk
+    fn get_columnar(&mut self, key: &Key) -> Option<Value> {
+        let index = key.to_usize();
+
+        // Not opened yet or opened before the file grew
+        if self.columnar.as_ref().map(|columnar| columnar.len()) <= Some(index) {
+            self.columnar = Columnar::open(&self.path_all.join(COLUMNAR_FILE_NAME))
+                .ok()
+                .map(Arc::new);
+        }
+
+        self.columnar.as_ref()?.get(index).unwrap()
+    }
+
+    fn export_columnar(
+        &self,
+        chunk_id: &ChunkId,
+        serialized: &Serialized,
+    ) -> color_eyre::Result<()> {
+        let path = self.path_all.join(COLUMNAR_FILE_NAME);
+
+        // Consecutive values, by index of the first one
+        let mut runs: Vec<(usize, Vec<Value>)> = vec![];
+
+        serialized
+            .entries(chunk_id)
+            .into_iter()
+            .for_each(|(key, value)| {
+                let index = key.to_usize();
+
+                match runs.last_mut() {
+                    Some((first_index, values)) if *first_index + values.len() == index => {
+                        values.push(value)
+                    }
+                    _ => runs.push((index, vec![value])),
+                }
+            });
+
+        runs.into_iter().try_for_each(|(first_index, values)| {
+            Columnar::write(&path, self.version, first_index, &values)
+        })
+    }
 }
 
 impl<Key, Value, ChunkId, Serialized> AnyMap for GenericMap<Key, Value, ChunkId, Serialized>
@@ -340,7 +548,8 @@ where
                         panic!();
                     });
 
-                    let serialized = self.serialization.import::<Serialized>(path).unwrap();
+                    let serialized =
+                        Self::_import_chunk(chunk_id, path, &self.serialization).unwrap();
 
                     self.imported.insert(*chunk_id, serialized);
                 }
@@ -367,14 +576,23 @@ where
                     panic!();
                 });
 
-                let path = self.path_all.join(chunk_id.to_name());
+                // Columnar files only hold the values of the map, last is thus a regular binary file
+                let serialization_last = if self.serialization == Serialization::Columnar {
+                    self.export_columnar(chunk_id, serialized)?;
+
+                    Serialization::Binary
+                } else {
+                    let path = self.path_all.join(chunk_id.to_name());
 
-                self.serialization.export(Path::new(&path), serialized)?;
+                    self.serialization.export(Path::new(&path), serialized)?;
+
+                    self.serialization
+                };
 
                 // Export last
                 if index == len - 1 {
                     if let Some(path_last) = self.path_last.as_ref() {
-                        self.serialization
+                        serialization_last
                             .export(Path::new(path_last), serialized.last().unwrap())?;
                     }
                 }
@@ -400,15 +618,89 @@ where
         self.to_insert.clear();
     }
 
+    fn evict(&mut self) {
+        if let Some(last) = self.imported.pop_last() {
+            self.imported = BTreeMap::from([last]);
+        }
+
+        self.to_insert = BTreeMap::default();
+
+        self.columnar = None;
+    }
+
     fn delete_files(&self) {
+        // Columnar chunks share the same file
         self.read_dir()
-            .iter()
-            .for_each(|(_, path)| fs::remove_file(path).unwrap())
+            .into_values()
+            .dedup()
+            .for_each(|path| fs::remove_file(path).unwrap())
     }
 
     fn kind(&self) -> MapKind {
         self.kind
     }
+
+    fn verify(&self, dates_without_blocks: &BTreeSet<Date>) -> Vec<String> {
+        let mut issues = vec![];
+
+        let mut previous_key: Option<Key> = None;
+
+        let mut non_finite = 0;
+
+        self.read_dir().into_iter().for_each(|(chunk_id, path)| {
+            let serialized = match self.import_chunk(&chunk_id, &path) {
+                Ok(serialized) => serialized,
+                Err(error) => {
+                    issues.push(format!("Failed to decode {path:?}: {error}"));
+                    return;
+                }
+            };
+
+            if serialized.version() != self.version {
+                issues.push(format!(
+                    "Version of {path:?} is {} instead of {}",
+                    serialized.version(),
+                    self.version
+                ));
+            }
+
+            serialized
+                .entries(&chunk_id)
+                .into_iter()
+                .for_each(|(key, value)| {
+                    if let Some(previous_key) = previous_key {
+                        let missing = previous_key
+                            .iter_up_to(&key)
+                            .filter(|between| *between != previous_key && *between != key)
+                            .filter(|between| {
+                                !between
+                                    .to_date()
+                                    .is_some_and(|date| dates_without_blocks.contains(&date))
+                            })
+                            .count();
+
+                        if missing > 0 {
+                            issues.push(format!(
+                                "Missing {missing} keys between {previous_key:?} and {key:?}"
+                            ));
+                        }
+                    }
+
+                    // Computed maps hold NaN on purpose, before `min_percentile_key` or when dividing by zero
+                    if self.kind == MapKind::Inserted && !value.is_finite() {
+                        non_finite += 1;
+                    }
+
+                    previous_key.replace(key);
+                });
+        });
+
+        if non_finite > 0 {
+            issues.push(format!("{non_finite} NaN or infinite values"));
+        }
+
+        issues
+    }
 }
 
 impl<Key, Value, ChunkId, Serialized> GenericMap<Key, Value, ChunkId, Serialized>
@@ -718,6 +1010,311 @@ where
         });
     }
 
+    /// Population standard deviation of the last `len` values, or of all of them if `None`, non finite values are ignored
+    pub fn multi_insert_standard_deviation<SourceValue, SourceSerialized>(
+        &mut self,
+        keys: &[Key],
+        source: &mut GenericMap<Key, SourceValue, ChunkId, SourceSerialized>,
+        len: Option<usize>,
+    ) where
+        SourceValue: MapValue,
+        SourceSerialized: MapSerialized<Key, SourceValue, ChunkId>,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<SourceValue>,
+    {
+        if len.is_some_and(|len| len < 2) {
+            panic!("Standard deviation of 1 or less is not useful");
+        }
+
+        let mut get = |key: Option<Key>| {
+            key.and_then(|key| source.get_or_import(&key))
+                .map(f64::lossy_from)
+                .filter(|value| value.is_finite())
+        };
+
+        // Count, sum and sum of squares of the values in the window
+        let mut window: Option<(f64, f64, f64)> = None;
+
+        keys.iter().for_each(|key| {
+            let (mut count, mut sum, mut squares) = window.unwrap_or_else(|| {
+                // Window ending at the previous key
+                (1..)
+                    .take(len.unwrap_or(usize::MAX))
+                    .map_while(|x| key.checked_sub(x))
+                    .take_while(|previous_key| !previous_key.is_out_of_bounds())
+                    .filter_map(|previous_key| get(Some(previous_key)))
+                    .fold((0.0, 0.0, 0.0), |(count, sum, squares), value| {
+                        (count + 1.0, sum + value, squares + value * value)
+                    })
+            });
+
+            if let Some(value) = get(Some(*key)) {
+                count += 1.0;
+                sum += value;
+                squares += value * value;
+            }
+
+            if let Some(value) = get(len.and_then(|len| key.checked_sub(len))) {
+                count -= 1.0;
+                sum -= value;
+                squares -= value * value;
+            }
+
+            window.replace((count, sum, squares));
+
+            let standard_deviation = if count > 0.0 {
+                let mean = sum / count;
+                (squares / count - mean * mean).max(0.0).sqrt()
+            } else {
+                0.0
+            };
+
+            self.insert_computed(*key, Value::lossy_from(standard_deviation));
+        });
+    }
+
+    /// Exponential moving average with a smoothing factor of `2 / (len + 1)`, seeded with the first value
+    pub fn multi_insert_exponential_average<SourceValue, SourceSerialized>(
+        &mut self,
+        keys: &[Key],
+        source: &mut GenericMap<Key, SourceValue, ChunkId, SourceSerialized>,
+        len: usize,
+    ) where
+        SourceValue: MapValue,
+        SourceSerialized: MapSerialized<Key, SourceValue, ChunkId>,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<SourceValue> + LossyFrom<Value>,
+    {
+        if len <= 1 {
+            panic!("Average of 1 or less is not useful");
+        }
+
+        let alpha = 2.0 / (len as f64 + 1.0);
+
+        let mut average: Option<f64> = None;
+
+        keys.iter().for_each(|key| {
+            let previous_average = average
+                .or_else(|| {
+                    key.checked_sub(1)
+                        .and_then(|previous_average_key| self.get_or_import(&previous_average_key))
+                        .map(f64::lossy_from)
+                })
+                .filter(|previous_average| previous_average.is_finite());
+
+            let last_value = source
+                .get_or_import(key)
+                .map(f64::lossy_from)
+                .filter(|value| value.is_finite());
+
+            let value = match (previous_average, last_value) {
+                (Some(previous_average), Some(last_value)) => {
+                    previous_average + alpha * (last_value - previous_average)
+                }
+                (previous_average, last_value) => {
+                    previous_average.or(last_value).unwrap_or_default()
+                }
+            };
+
+            average.replace(value);
+
+            self.insert_computed(*key, Value::lossy_from(value));
+        });
+    }
+
+    /// Number of standard deviations between the source and its average, 0 when there is no deviation or a value is missing
+    pub fn multi_insert_z_score<SourceValue, SourceSerialized>(
+        &mut self,
+        keys: &[Key],
+        source: &mut GenericMap<Key, SourceValue, ChunkId, SourceSerialized>,
+        average: &mut Self,
+        standard_deviation: &mut Self,
+    ) where
+        SourceValue: MapValue,
+        SourceSerialized: MapSerialized<Key, SourceValue, ChunkId>,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<SourceValue> + LossyFrom<Value>,
+    {
+        keys.iter().for_each(|key| {
+            let value = source
+                .get_or_import(key)
+                .map(f64::lossy_from)
+                .filter(|value| value.is_finite());
+            let average = average
+                .get_or_import(key)
+                .map(f64::lossy_from)
+                .filter(|average| average.is_finite());
+            let standard_deviation = standard_deviation
+                .get_or_import(key)
+                .map(f64::lossy_from)
+                .filter(|standard_deviation| standard_deviation.is_finite());
+
+            let z_score = match (value, average, standard_deviation) {
+                (Some(value), Some(average), Some(standard_deviation))
+                    if standard_deviation > 0.0 =>
+                {
+                    (value - average) / standard_deviation
+                }
+                _ => 0.0,
+            };
+
+            self.insert_computed(*key, Value::lossy_from(z_score));
+        });
+    }
+
+    /// `average + multiplier * standard_deviation`, a negative multiplier gives the lower band, 0 when the average is missing
+    pub fn multi_insert_bollinger_band(
+        &mut self,
+        keys: &[Key],
+        average: &mut Self,
+        standard_deviation: &mut Self,
+        multiplier: f64,
+    ) where
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<Value>,
+    {
+        keys.iter().for_each(|key| {
+            let standard_deviation = standard_deviation
+                .get_or_import(key)
+                .map(f64::lossy_from)
+                .filter(|standard_deviation| standard_deviation.is_finite())
+                .unwrap_or_default();
+
+            let band = average
+                .get_or_import(key)
+                .map(f64::lossy_from)
+                .filter(|average| average.is_finite())
+                .map_or(0.0, |average| average + multiplier * standard_deviation);
+
+            self.insert_computed(*key, Value::lossy_from(band));
+        });
+    }
+
+    /// Pearson correlation of the last `len` pairs of values
+    pub fn multi_insert_correlation<A, B, ASerialized, BSerialized>(
+        &mut self,
+        keys: &[Key],
+        a: &mut GenericMap<Key, A, ChunkId, ASerialized>,
+        b: &mut GenericMap<Key, B, ChunkId, BSerialized>,
+        len: usize,
+    ) where
+        A: MapValue,
+        ASerialized: MapSerialized<Key, A, ChunkId>,
+        B: MapValue,
+        BSerialized: MapSerialized<Key, B, ChunkId>,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<A> + LossyFrom<B>,
+    {
+        self._multi_insert_covariance(keys, a, b, len, |covariance, a_variance, b_variance| {
+            covariance / (a_variance * b_variance).sqrt()
+        });
+    }
+
+    /// Sensitivity of `a` to `b` over the last `len` pairs of values, `covariance(a, b) / variance(b)`
+    pub fn multi_insert_beta<A, B, ASerialized, BSerialized>(
+        &mut self,
+        keys: &[Key],
+        a: &mut GenericMap<Key, A, ChunkId, ASerialized>,
+        b: &mut GenericMap<Key, B, ChunkId, BSerialized>,
+        len: usize,
+    ) where
+        A: MapValue,
+        ASerialized: MapSerialized<Key, A, ChunkId>,
+        B: MapValue,
+        BSerialized: MapSerialized<Key, B, ChunkId>,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<A> + LossyFrom<B>,
+    {
+        self._multi_insert_covariance(keys, a, b, len, |covariance, _, b_variance| {
+            covariance / b_variance
+        });
+    }
+
+    /// Keeps the sums of the last `len` pairs where both values are finite and inserts `transform(covariance, a_variance, b_variance)`, or 0 if it isn't finite
+    fn _multi_insert_covariance<A, B, ASerialized, BSerialized, F>(
+        &mut self,
+        keys: &[Key],
+        a: &mut GenericMap<Key, A, ChunkId, ASerialized>,
+        b: &mut GenericMap<Key, B, ChunkId, BSerialized>,
+        len: usize,
+        transform: F,
+    ) where
+        A: MapValue,
+        ASerialized: MapSerialized<Key, A, ChunkId>,
+        B: MapValue,
+        BSerialized: MapSerialized<Key, B, ChunkId>,
+        Value: LossyFrom<f64>,
+        f64: LossyFrom<A> + LossyFrom<B>,
+        F: Fn(f64, f64, f64) -> f64,
+    {
+        if len < 2 {
+            panic!("Covariance of 1 or less is not useful");
+        }
+
+        let mut get = |key: Option<Key>| {
+            let key = key?;
+
+            let a = f64::lossy_from(a.get_or_import(&key)?);
+            let b = f64::lossy_from(b.get_or_import(&key)?);
+
+            (a.is_finite() && b.is_finite()).then_some((a, b))
+        };
+
+        // Count, sum of a, sum of b, sum of a², sum of b² and sum of a * b of the pairs in the window
+        let mut window: Option<[f64; 6]> = None;
+
+        let add = |sums: &mut [f64; 6], (a, b): (f64, f64), sign: f64| {
+            sums[0] += sign;
+            sums[1] += sign * a;
+            sums[2] += sign * b;
+            sums[3] += sign * a * a;
+            sums[4] += sign * b * b;
+            sums[5] += sign * a * b;
+        };
+
+        keys.iter().for_each(|key| {
+            let mut sums = window.unwrap_or_else(|| {
+                // Window ending at the previous key
+                let mut sums = [0.0; 6];
+
+                (1..=len)
+                    .map_while(|x| key.checked_sub(x))
+                    .take_while(|previous_key| !previous_key.is_out_of_bounds())
+                    .filter_map(|previous_key| get(Some(previous_key)))
+                    .for_each(|pair| add(&mut sums, pair, 1.0));
+
+                sums
+            });
+
+            if let Some(pair) = get(Some(*key)) {
+                add(&mut sums, pair, 1.0);
+            }
+
+            if let Some(pair) = get(key.checked_sub(len)) {
+                add(&mut sums, pair, -1.0);
+            }
+
+            window.replace(sums);
+
+            let [count, a_sum, b_sum, a_squares, b_squares, products] = sums;
+
+            let value = if count > 1.0 {
+                let a_mean = a_sum / count;
+                let b_mean = b_sum / count;
+
+                let covariance = products / count - a_mean * b_mean;
+                let a_variance = (a_squares / count - a_mean * a_mean).max(0.0);
+                let b_variance = (b_squares / count - b_mean * b_mean).max(0.0);
+
+                Some(transform(covariance, a_variance, b_variance)).filter(|v| v.is_finite())
+            } else {
+                None
+            };
+
+            self.insert_computed(*key, Value::lossy_from(value.unwrap_or_default()));
+        });
+    }
+
     pub fn multi_insert_net_change(&mut self, keys: &[Key], source: &mut Self, len: usize)
     where
         Value: Sub<Output = Value>,
@@ -929,3 +1526,335 @@ where
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::BTreeSet,
+        env, fs,
+        path::Path,
+        sync::{Mutex, MutexGuard},
+    };
+
+    use chrono::NaiveDate;
+
+    use crate::{
+        io::{Checkpoint, Serialization, COLUMNAR_FILE_NAME},
+        structs::{AnyMap, Date, DateMap, Height, HeightMap, MapKey, MapKind, MapSerialized},
+    };
+
+    /// An open checkpoint stages the exports of every thread
+    static EXPORTS: Mutex<()> = Mutex::new(());
+
+    fn lock_exports() -> MutexGuard<'static, ()> {
+        EXPORTS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> Date {
+        Date::wrap(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+    }
+
+    fn export(map: &mut DateMap<f32>) {
+        map.pre_export();
+        map.export().unwrap();
+        map.post_export();
+    }
+
+    #[test]
+    fn verify_accepts_percentile_nans_and_days_without_blocks() {
+        let _exports = lock_exports();
+
+        let path = env::temp_dir().join("verify/percentile");
+
+        let _ = fs::remove_dir_all(&path);
+
+        let mut map = DateMap::<f32>::new_bin(1, MapKind::Computed, path.to_str().unwrap());
+
+        // No block was mined between the genesis and the 9th
+        let dates_without_blocks = date(2009, 1, 4)
+            .iter_up_to(&date(2009, 1, 8))
+            .collect::<BTreeSet<_>>();
+
+        [date(2009, 1, 3)]
+            .into_iter()
+            .chain(date(2009, 1, 9).iter_up_to(&date(2012, 2, 1)))
+            .for_each(|date| {
+                let value = if date < Date::min_percentile_key() {
+                    f32::NAN
+                } else {
+                    1.0
+                };
+
+                map.insert_computed(date, value);
+            });
+
+        export(&mut map);
+
+        assert_eq!(map.verify(&dates_without_blocks), Vec::<String>::new());
+
+        assert_eq!(
+            map.verify(&BTreeSet::default()),
+            vec![format!(
+                "Missing 5 keys between {:?} and {:?}",
+                date(2009, 1, 3),
+                date(2009, 1, 9)
+            )]
+        );
+
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    #[test]
+    fn verify_reports_nans_of_inserted_maps() {
+        let _exports = lock_exports();
+
+        let path = env::temp_dir().join("verify/inserted");
+
+        let _ = fs::remove_dir_all(&path);
+
+        let mut map = DateMap::<f32>::new_bin(1, MapKind::Inserted, path.to_str().unwrap());
+
+        map.insert(date(2009, 1, 3), f32::NAN);
+        map.insert(date(2009, 1, 4), f32::INFINITY);
+        map.insert(date(2009, 1, 5), 1.0);
+
+        export(&mut map);
+
+        assert_eq!(
+            map.verify(&BTreeSet::default()),
+            vec!["2 NaN or infinite values".to_owned()]
+        );
+
+        let _ = fs::remove_dir_all(&path);
+    }
+
+    fn imported(path: &Path) -> Option<f32> {
+        DateMap::<f32>::new_bin(1, MapKind::Computed, path.to_str().unwrap())
+            .get_or_import(&date(2009, 1, 3))
+    }
+
+    /// Exports 1.0 and then 2.0 within a checkpoint that is dropped before its renames if `prepare` and before its commit point otherwise
+    fn interrupted_checkpoint(folder: &Path, prepare: bool) {
+        let _ = fs::remove_dir_all(folder);
+
+        fs::create_dir_all(folder).unwrap();
+
+        let path = folder.join("map");
+
+        let mut map = DateMap::<f32>::new_bin(1, MapKind::Computed, path.to_str().unwrap());
+
+        map.insert_computed(date(2009, 1, 3), 1.0);
+
+        export(&mut map);
+
+        let checkpoint = Checkpoint::begin_in(folder, Height::new(0), date(2009, 1, 3)).unwrap();
+
+        map.insert_computed(date(2009, 1, 3), 2.0);
+
+        export(&mut map);
+
+        if prepare {
+            drop(checkpoint.prepare().unwrap());
+        } else {
+            drop(checkpoint);
+        }
+
+        assert_eq!(imported(&path), Some(1.0));
+    }
+
+    #[test]
+    fn checkpoint_recovery_finishes_a_prepared_checkpoint() {
+        let _exports = lock_exports();
+
+        let folder = env::temp_dir().join("checkpoint/prepared");
+
+        interrupted_checkpoint(&folder, true);
+
+        Checkpoint::recover_in(&folder).unwrap();
+
+        assert_eq!(imported(&folder.join("map")), Some(2.0));
+        assert!(!folder.join("checkpoint.json").exists());
+
+        let _ = fs::remove_dir_all(&folder);
+    }
+
+    #[test]
+    fn checkpoint_recovery_discards_an_unprepared_checkpoint() {
+        let _exports = lock_exports();
+
+        let folder = env::temp_dir().join("checkpoint/unprepared");
+
+        interrupted_checkpoint(&folder, false);
+
+        Checkpoint::recover_in(&folder).unwrap();
+
+        assert_eq!(imported(&folder.join("map")), Some(1.0));
+        assert!(!folder.join("checkpoint.json").exists());
+
+        let _ = fs::remove_dir_all(&folder);
+    }
+
+    fn columnar_map(path: &Path) -> DateMap<f32> {
+        DateMap::<f32>::new(
+            1,
+            MapKind::Computed,
+            path.to_str().unwrap(),
+            Serialization::Columnar,
+            1,
+            true,
+        )
+    }
+
+    #[test]
+    fn columnar_holes_are_absent() {
+        let _exports = lock_exports();
+
+        let path = env::temp_dir().join("columnar/holes");
+
+        let _ = fs::remove_dir_all(&path);
+
+        let mut map = columnar_map(&path);
+
+        map.insert_computed(date(2009, 1, 3), 1.0);
+        map.insert_computed(date(2009, 1, 9), 2.0);
+
+        export(&mut map);
+
+        let mut map = columnar_map(&path);
+
+        assert_eq!(map.get_or_import(&date(2009, 1, 5)), None);
+        assert_eq!(map.get_or_import(&date(2009, 1, 9)), Some(2.0));
+
+        let path = map.path().to_owned();
+
+        let serialization = Serialization::Columnar;
+
+        assert!(path.join(COLUMNAR_FILE_NAME).exists());
+
+        assert_eq!(
+            DateMap::<f32>::_import_key(&path, &serialization, &date(2009, 1, 5)).unwrap(),
+            None
+        );
+        assert_eq!(
+            DateMap::<f32>::_import_last(&path, &serialization).unwrap(),
+            Some((date(2009, 1, 9), 2.0))
+        );
+
+        let chunks = DateMap::<f32>::_read_dir(&path, &serialization);
+
+        assert_eq!(chunks.len(), 1);
+
+        let (chunk_id, chunk_path) = chunks.first_key_value().unwrap();
+
+        assert_eq!(
+            DateMap::<f32>::_import_chunk(chunk_id, chunk_path, &serialization)
+                .unwrap()
+                .entries(chunk_id),
+            vec![(date(2009, 1, 3), 1.0), (date(2009, 1, 9), 2.0)]
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    fn height_map(name: &str, values: &[f64]) -> HeightMap<f64> {
+        let path = env::temp_dir().join(format!("stats/{name}"));
+
+        let _ = fs::remove_dir_all(&path);
+
+        let mut map = HeightMap::<f64>::new_bin(1, MapKind::Computed, path.to_str().unwrap());
+
+        values.iter().enumerate().for_each(|(height, value)| {
+            map.insert_computed(Height::new(height as u32), *value);
+        });
+
+        map
+    }
+
+    fn heights(range: std::ops::Range<u32>) -> Vec<Height> {
+        range.map(Height::new).collect()
+    }
+
+    fn assert_values(map: &mut HeightMap<f64>, expected: &[f64]) {
+        expected.iter().enumerate().for_each(|(height, expected)| {
+            let value = map.get_or_import(&Height::new(height as u32)).unwrap();
+
+            assert!(
+                (value - expected).abs() < 1e-9,
+                "{value} instead of {expected} at {height}"
+            );
+        });
+    }
+
+    #[test]
+    fn exponential_average_is_seeded_with_the_first_value() {
+        let mut source = height_map("ema/source", &[1.0, 2.0, 3.0, 4.0]);
+        let mut ema = height_map("ema/ema", &[]);
+
+        ema.multi_insert_exponential_average(&heights(0..2), &mut source, 3);
+
+        // Resumes from the last average
+        ema.multi_insert_exponential_average(&heights(2..4), &mut source, 3);
+
+        assert_values(&mut ema, &[1.0, 1.5, 2.25, 3.125]);
+    }
+
+    #[test]
+    fn standard_deviation_removes_the_value_leaving_the_window() {
+        let mut source = height_map("sd/source", &[1.0, 3.0, 10.0, 10.0]);
+        let mut sd = height_map("sd/sd", &[]);
+        let mut resumed = height_map("sd/resumed", &[]);
+
+        sd.multi_insert_standard_deviation(&heights(0..4), &mut source, Some(2));
+
+        // Window rebuilt from the previous keys
+        resumed.multi_insert_standard_deviation(&heights(3..4), &mut source, Some(2));
+
+        assert_values(&mut sd, &[0.0, 1.0, 3.5, 0.0]);
+        assert_eq!(resumed.get_or_import(&Height::new(3)), Some(0.0));
+    }
+
+    #[test]
+    fn correlation_of_identical_series_is_one() {
+        let values = [1.0, 2.0, 4.0, 8.0, 16.0];
+
+        let mut a = height_map("correlation/a", &values);
+        let mut b = height_map("correlation/b", &values);
+        let mut correlation = height_map("correlation/correlation", &[]);
+
+        correlation.multi_insert_correlation(&heights(0..5), &mut a, &mut b, 3);
+
+        // A single pair has no correlation
+        assert_values(&mut correlation, &[0.0, 1.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn beta_of_an_affine_series_is_its_slope() {
+        let b_values = [1.0, 3.0, 2.0, 5.0, 4.0];
+        let a_values = b_values.map(|b| 2.0 * b + 1.0);
+
+        let mut a = height_map("beta/a", &a_values);
+        let mut b = height_map("beta/b", &b_values);
+        let mut beta = height_map("beta/beta", &[]);
+
+        beta.multi_insert_beta(&heights(0..5), &mut a, &mut b, 3);
+
+        assert_values(&mut beta, &[0.0, 2.0, 2.0, 2.0, 2.0]);
+    }
+
+    #[test]
+    fn z_score_and_bollinger_band_handle_missing_values() {
+        let mut source = height_map("missing/source", &[1.0, 2.0, 3.0]);
+        let mut average = height_map("missing/average", &[1.0, 1.5]);
+        let mut sd = height_map("missing/sd", &[0.0, 0.5]);
+        let mut z_score = height_map("missing/z_score", &[]);
+        let mut band = height_map("missing/band", &[]);
+
+        z_score.multi_insert_z_score(&heights(0..3), &mut source, &mut average, &mut sd);
+        band.multi_insert_bollinger_band(&heights(0..3), &mut average, &mut sd, 2.0);
+
+        assert_values(&mut z_score, &[0.0, 1.0, 0.0]);
+        assert_values(&mut band, &[1.0, 2.5, 0.0]);
+    }
+}
@@ -117,6 +117,10 @@ impl MapKey<HeightMapChunkId> for Height {
         HeightMapChunkId::new(self)
     }
 
+    fn from_chunk_id(chunk_id: &HeightMapChunkId) -> Self {
+        **chunk_id
+    }
+
     fn to_first_unsafe(&self) -> Option<Self> {
         let offset = NUMBER_OF_UNSAFE_BLOCKS - 1;
 
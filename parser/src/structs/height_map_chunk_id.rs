@@ -48,4 +48,8 @@ impl MapChunkId for HeightMapChunkId {
     fn from_usize(id: usize) -> Self {
         Self(Height::new(id as u32))
     }
+
+    fn next(self) -> Self {
+        Self(*self + HEIGHT_MAP_CHUNK_SIZE)
+    }
 }
@@ -0,0 +1,226 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use color_eyre::eyre::eyre;
+
+use crate::{
+    datasets::DATASETS_PATH,
+    io::{Json, OUTPUTS_FOLDER_PATH},
+};
+
+/// Paths of the maps, indexed by their id
+static PATHS: Mutex<Vec<PathBuf>> = Mutex::new(vec![]);
+
+/// Pairs of (computed map id, source map id) found during this run
+static EDGES: Mutex<BTreeSet<(usize, usize)>> = Mutex::new(BTreeSet::new());
+
+static RECOMPUTE: Mutex<Option<Recompute>> = Mutex::new(None);
+
+thread_local! {
+    /// Maps read since the first read that followed an insert, on this thread
+    static READS: RefCell<BTreeSet<usize>> = const { RefCell::new(BTreeSet::new()) };
+
+    /// Whether a computed map was inserted since the last read, on this thread
+    static INSERTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Computed map path to the paths of the maps it was computed from
+type Graph = BTreeMap<PathBuf, BTreeSet<PathBuf>>;
+
+struct Recompute {
+    pattern: String,
+    dependents: BTreeSet<PathBuf>,
+}
+
+///
+/// Which maps each computed map is computed from.
+///
+/// Sources are passed around freely in the compute functions (closures, `multi_insert_*`, manual loops)
+/// so instead of declaring them, every read is remembered and attributed to the computed inserts that follow on the same thread,
+/// until the next read after an insert starts a new set of reads.
+/// Several maps computed from the same reads (recaps for example) thus all get them as sources.
+/// The graph is merged into a file at each export and used to also recompute the dependents of the maps matching `--recompute`,
+/// which is refused until a graph was saved.
+///
+pub struct MapDependencies;
+
+impl MapDependencies {
+    pub const FILE_NAME: &'static str = "map_dependencies.json";
+
+    pub fn register(path: &Path) -> usize {
+        let mut paths = PATHS.lock().unwrap();
+
+        paths.push(path.to_owned());
+
+        paths.len() - 1
+    }
+
+    #[inline(always)]
+    pub fn record_read(id: usize) {
+        READS.with_borrow_mut(|reads| {
+            if INSERTED.replace(false) {
+                reads.clear();
+            }
+
+            reads.insert(id);
+        });
+    }
+
+    /// Forgets the reads that weren't followed by a computed insert
+    pub fn clear_reads() {
+        INSERTED.set(false);
+        READS.with_borrow_mut(|reads| reads.clear());
+    }
+
+    /// Attributes the current reads to the computed map, `known` being the sources already attributed to it
+    ///
+    /// The reads are kept for the following inserts until the next read
+    #[inline(always)]
+    pub fn record_insert(id: usize, known: &mut BTreeSet<usize>) {
+        INSERTED.set(true);
+
+        READS.with_borrow(|reads| {
+            if reads.is_empty() {
+                return;
+            }
+
+            let new = reads
+                .iter()
+                .filter(|source| **source != id && !known.contains(source))
+                .copied()
+                .collect::<Vec<_>>();
+
+            if new.is_empty() {
+                return;
+            }
+
+            let mut edges = EDGES.lock().unwrap();
+
+            new.into_iter().for_each(|source| {
+                known.insert(source);
+                edges.insert((id, source));
+            });
+        });
+    }
+
+    fn path() -> PathBuf {
+        Path::new(OUTPUTS_FOLDER_PATH).join(Self::FILE_NAME)
+    }
+
+    fn import() -> Graph {
+        Json::import(&Self::path()).unwrap_or_default()
+    }
+
+    /// Merges the dependencies found during this run with the saved ones
+    pub fn export() -> color_eyre::Result<()> {
+        let mut graph = Self::import();
+
+        let paths = PATHS.lock().unwrap();
+
+        EDGES.lock().unwrap().iter().for_each(|(id, source)| {
+            graph
+                .entry(paths[*id].clone())
+                .or_default()
+                .insert(paths[*source].clone());
+        });
+
+        Json::export(&Self::path(), &graph)
+    }
+
+    /// Needs to be called before importing the datasets
+    ///
+    /// Fails if no graph was saved yet since the dependents of the matching maps would silently be left as is
+    pub fn start_recomputing(glob: &str) -> color_eyre::Result<()> {
+        // Same transformation as the map paths
+        let pattern = glob.replace(['-', '_', ' '], "/");
+
+        let graph = Self::import();
+
+        if graph.is_empty() {
+            return Err(eyre!(
+                "No dependency graph saved yet, run the parser once without --recompute until it exports to save one"
+            ));
+        }
+
+        let mut dependents_by_source: BTreeMap<&Path, Vec<&Path>> = BTreeMap::new();
+
+        graph.iter().for_each(|(dependent, sources)| {
+            sources.iter().for_each(|source| {
+                dependents_by_source
+                    .entry(source)
+                    .or_default()
+                    .push(dependent);
+            });
+        });
+
+        let mut queue = graph
+            .keys()
+            .map(|path| path.as_path())
+            .chain(dependents_by_source.keys().copied())
+            .filter(|path| Self::matches(&pattern, path))
+            .collect::<VecDeque<_>>();
+
+        let mut dependents = BTreeSet::new();
+
+        while let Some(path) = queue.pop_front() {
+            if let Some(paths) = dependents_by_source.get(path) {
+                paths.iter().for_each(|dependent| {
+                    if dependents.insert(dependent.to_path_buf()) {
+                        queue.push_back(dependent);
+                    }
+                });
+            }
+        }
+
+        RECOMPUTE.lock().unwrap().replace(Recompute {
+            pattern,
+            dependents,
+        });
+
+        Ok(())
+    }
+
+    pub fn stop_recomputing() {
+        RECOMPUTE.lock().unwrap().take();
+    }
+
+    pub fn should_recompute(path: &Path) -> bool {
+        RECOMPUTE.lock().unwrap().as_ref().is_some_and(
+            |Recompute {
+                 pattern,
+                 dependents,
+             }| { dependents.contains(path) || Self::matches(pattern, path) },
+        )
+    }
+
+    /// Matches the folder of the map relatively to the datasets folder, `price/1w/sma/ratio` for example
+    fn matches(pattern: &str, path: &Path) -> bool {
+        let Some(folder) = path.parent().and_then(|folder| folder.to_str()) else {
+            return false;
+        };
+
+        let folder = folder
+            .strip_prefix(&format!("{DATASETS_PATH}/"))
+            .or_else(|| folder.strip_prefix("../"))
+            .unwrap_or(folder);
+
+        glob_match(pattern.as_bytes(), folder.as_bytes())
+    }
+}
+
+/// `*` matches any sequence of characters, including `/`, and `?` any single character
+fn glob_match(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.first(), text.first()) {
+        (None, None) => true,
+        (Some(b'*'), _) => {
+            glob_match(&pattern[1..], text) || (!text.is_empty() && glob_match(pattern, &text[1..]))
+        }
+        (Some(b'?'), Some(_)) => glob_match(&pattern[1..], &text[1..]),
+        (Some(p), Some(t)) if p == t => glob_match(&pattern[1..], &text[1..]),
+        _ => false,
+    }
+}
@@ -19,6 +19,10 @@ pub trait MapValue:
     + Send
     + Allocative
 {
+    /// Only floats can be NaN or infinite
+    fn is_finite(&self) -> bool {
+        true
+    }
 }
 
 impl MapValue for u8 {}
@@ -26,9 +30,23 @@ impl MapValue for u16 {}
 impl MapValue for u32 {}
 impl MapValue for u64 {}
 impl MapValue for usize {}
-impl MapValue for f32 {}
-impl MapValue for f64 {}
+impl MapValue for f32 {
+    fn is_finite(&self) -> bool {
+        f32::is_finite(*self)
+    }
+}
+impl MapValue for f64 {
+    fn is_finite(&self) -> bool {
+        f64::is_finite(*self)
+    }
+}
 impl MapValue for Date {}
-impl MapValue for OHLC {}
+impl MapValue for OHLC {
+    fn is_finite(&self) -> bool {
+        [self.open, self.high, self.low, self.close]
+            .iter()
+            .all(|v| v.is_finite())
+    }
+}
 impl MapValue for Height {}
 impl MapValue for Timestamp {}
@@ -1,4 +1,5 @@
 mod address;
+mod address_activity;
 mod address_data;
 mod address_liquidity;
 mod address_realized_data;
@@ -17,6 +18,7 @@ mod date;
 mod date_data;
 mod date_map;
 mod date_map_chunk_id;
+mod distribution;
 mod empty_address_data;
 mod epoch;
 mod exit;
@@ -25,10 +27,12 @@ mod height;
 mod height_map;
 mod height_map_chunk_id;
 mod liquidity;
+mod map_dependencies;
 mod map_value;
 mod ohlc;
 mod partial_txout_data;
 mod price;
+mod received_data;
 mod sent_data;
 mod serialized_btreemap;
 mod serialized_vec;
@@ -37,6 +41,7 @@ mod tx_data;
 mod txout_index;
 
 pub use address::*;
+pub use address_activity::*;
 pub use address_data::*;
 pub use address_liquidity::*;
 pub use address_realized_data::*;
@@ -55,6 +60,7 @@ pub use date::*;
 pub use date_data::*;
 pub use date_map::*;
 pub use date_map_chunk_id::*;
+pub use distribution::*;
 pub use empty_address_data::*;
 pub use epoch::*;
 pub use exit::*;
@@ -63,10 +69,12 @@ pub use height::*;
 pub use height_map::*;
 pub use height_map_chunk_id::*;
 pub use liquidity::*;
+pub use map_dependencies::*;
 pub use map_value::*;
 pub use ohlc::*;
 pub use partial_txout_data::*;
 pub use price::*;
+pub use received_data::*;
 pub use sent_data::*;
 pub use serialized_btreemap::*;
 pub use serialized_vec::*;
@@ -0,0 +1,14 @@
+use super::Amount;
+
+#[derive(Default, Debug)]
+pub struct ReceivedData {
+    pub volume: Amount,
+    pub count: u32,
+}
+
+impl ReceivedData {
+    pub fn receive(&mut self, amount: Amount) {
+        self.volume += amount;
+        self.count += 1;
+    }
+}
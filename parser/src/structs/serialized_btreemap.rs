@@ -6,9 +6,9 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::Serialization;
 
-use super::{DateMap, MapChunkId, MapKey, MapSerialized, MapValue};
+use super::{GenericMap, MapChunkId, MapKey, MapSerialized, MapValue};
 
-#[derive(Debug, Default, Serialize, Deserialize, Encode, Decode, Allocative)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Encode, Decode, Allocative)]
 pub struct SerializedBTreeMap<Key, Value>
 where
     Key: Ord,
@@ -30,10 +30,15 @@ where
     {
         let mut s = None;
 
-        DateMap::<usize>::_read_dir(path, serialization)
+        GenericMap::<Key, Value, ChunkId, Self>::_read_dir(path, serialization)
             .iter()
-            .for_each(|(_, path)| {
-                let map = serialization.import::<Self>(path).unwrap();
+            .for_each(|(chunk_id, path)| {
+                let map = GenericMap::<Key, Value, ChunkId, Self>::_import_chunk(
+                    chunk_id,
+                    path,
+                    serialization,
+                )
+                .unwrap();
 
                 if s.is_none() {
                     s.replace(map);
@@ -77,6 +82,10 @@ where
         self.map.last_key_value().map(|(_, v)| v)
     }
 
+    fn entries(&self, _: &ChunkId) -> Vec<(Key, Value)> {
+        self.map.iter().map(|(k, v)| (*k, *v)).collect()
+    }
+
     fn extend(&mut self, map: BTreeMap<Key, Value>) {
         self.map.extend(map)
     }
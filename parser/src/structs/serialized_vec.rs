@@ -8,7 +8,7 @@ use crate::Serialization;
 
 use super::{HeightMap, MapChunkId, MapKey, MapSerialized, MapValue};
 
-#[derive(Debug, Default, Serialize, Deserialize, Encode, Decode, Allocative)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, Encode, Decode, Allocative)]
 pub struct SerializedVec<Value> {
     version: u32,
     pub map: Vec<Value>,
@@ -22,10 +22,11 @@ impl<Value> SerializedVec<Value> {
     {
         let mut s = None;
 
-        HeightMap::<usize>::_read_dir(path, serialization)
+        HeightMap::<Value>::_read_dir(path, serialization)
             .iter()
-            .for_each(|(_, path)| {
-                let mut map = serialization.import::<Self>(path).unwrap();
+            .for_each(|(chunk_id, path)| {
+                let mut map =
+                    HeightMap::<Value>::_import_chunk(chunk_id, path, serialization).unwrap();
 
                 if s.is_none() {
                     s.replace(map);
@@ -73,6 +74,14 @@ where
         self.map.last()
     }
 
+    fn entries(&self, chunk_id: &ChunkId) -> Vec<(Key, Value)> {
+        self.map
+            .iter()
+            .enumerate()
+            .map(|(index, value)| (Key::from_usize(chunk_id.to_usize() + index), *value))
+            .collect()
+    }
+
     fn extend(&mut self, map: BTreeMap<Key, Value>) {
         map.into_iter().for_each(|(key, value)| {
             let key = key.to_serialized_key().to_usize();
@@ -1,9 +1,10 @@
 use allocative::Allocative;
+use bincode::{Decode, Encode};
 use sanakirja::{direct_repr, Storable, UnsizedStorable};
 
 use super::BlockPath;
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Allocative)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, Allocative)]
 pub struct TxData {
     pub index: u32,
     pub block_path: BlockPath,
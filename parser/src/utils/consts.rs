@@ -1,6 +1,7 @@
 pub const BYTES_IN_MB: usize = 1_000_000;
 
 pub const TARGET_BLOCKS_PER_DAY: usize = 144;
+pub const BLOCKS_PER_DIFFICULTY_EPOCH: usize = 2016;
 
 pub const ONE_DAY_IN_DAYS: usize = 1;
 pub const ONE_WEEK_IN_DAYS: usize = 7;
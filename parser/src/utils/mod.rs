@@ -3,6 +3,7 @@ mod flamegraph;
 mod log;
 mod lossy;
 mod percentile;
+mod ram;
 mod retry;
 mod rpc;
 mod time;
@@ -12,6 +13,7 @@ pub use flamegraph::*;
 pub use log::*;
 pub use lossy::*;
 pub use percentile::*;
+pub use ram::*;
 pub use retry::*;
 pub use rpc::*;
 pub use time::*;
@@ -0,0 +1,61 @@
+/// Resident set size of the process in bytes
+pub fn ram_usage() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    let kilobytes = std::fs::read_to_string("/proc/self/status")
+        .ok()?
+        .lines()
+        .find(|line| line.starts_with("VmRSS:"))?
+        .split_whitespace()
+        .nth(1)?
+        .parse::<u64>()
+        .ok()?;
+
+    #[cfg(not(target_os = "linux"))]
+    let kilobytes = String::from_utf8(
+        std::process::Command::new("ps")
+            .args(["-o", "rss=", "-p", &std::process::id().to_string()])
+            .output()
+            .ok()?
+            .stdout,
+    )
+    .ok()?
+    .trim()
+    .parse::<u64>()
+    .ok()?;
+
+    // Kibibytes despite the name
+    Some(kilobytes * 1024)
+}
+
+///
+/// Tells when to export early to stay under `--max-ram`.
+///
+/// The allocator doesn't always give freed memory back to the system,
+/// so when an export isn't enough to get back under the budget, the next one happens once usage grew by a tenth of it.
+///
+pub struct RamBudget {
+    max: u64,
+    floor: u64,
+}
+
+impl RamBudget {
+    pub fn new(max_gigabytes: f64) -> Self {
+        Self {
+            max: (max_gigabytes * 1_000_000_000.0) as u64,
+            floor: 0,
+        }
+    }
+
+    pub fn is_exceeded(&self) -> bool {
+        ram_usage().is_some_and(|usage| usage > self.max.max(self.floor + self.max / 10))
+    }
+
+    /// Returns whether usage is still over the budget
+    pub fn flushed(&mut self) -> bool {
+        let usage = ram_usage().unwrap_or_default();
+
+        self.floor = usage;
+
+        usage > self.max
+    }
+}
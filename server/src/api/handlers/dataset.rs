@@ -6,16 +6,17 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use color_eyre::{eyre::eyre, owo_colors::OwoColorize};
+use itertools::Itertools;
 use reqwest::StatusCode;
 use serde::Deserialize;
 
 use parser::{
-    log, Date, DateMap, Height, HeightMap, Json, MapChunkId, COMPRESSED_BIN_EXTENSION,
-    HEIGHT_MAP_CHUNK_SIZE, JSON_EXTENSION, OHLC,
+    log, Date, DateMap, Distribution, Height, HeightMap, Json, MapChunkId,
+    COMPRESSED_BIN_EXTENSION, HEIGHT_MAP_CHUNK_SIZE, JSON_EXTENSION, OHLC,
 };
 
 use crate::{
-    api::structs::{Chunk, Kind, Route},
+    api::structs::{Chunk, Kind, Route, Validators},
     header_map::HeaderMapUtils,
     AppState,
 };
@@ -57,7 +58,7 @@ fn _dataset_handler(
     headers: HeaderMap,
     Path(path): Path<String>,
     query: Query<Params>,
-    AppState { routes }: AppState,
+    AppState { routes, cache, .. }: AppState,
 ) -> color_eyre::Result<Response> {
     if query.chunk.is_some() && query.all.is_some() {
         return Err(eyre!("chunk and all are exclusive"));
@@ -98,50 +99,124 @@ fn _dataset_handler(
 
     let mut chunk = None;
 
-    if query.all.map_or(true, |b| !b) {
-        match kind {
-            Kind::Date => {
-                let datasets = DateMap::<usize>::_read_dir(&route.file_path, &route.serialization);
+    let all = query.all.is_some_and(|all| all);
 
+    let paths = match kind {
+        Kind::Date => {
+            let datasets = DateMap::<usize>::_read_dir(&route.file_path, &route.serialization);
+
+            if all {
+                datasets.into_values().dedup().collect_vec()
+            } else {
                 process_datasets(&headers, kind, &mut chunk, &mut route, query, datasets)?;
+                vec![route.file_path.clone()]
             }
-            Kind::Height => {
-                let datasets =
-                    HeightMap::<usize>::_read_dir(&route.file_path, &route.serialization);
+        }
+        Kind::Height => {
+            let datasets = HeightMap::<usize>::_read_dir(&route.file_path, &route.serialization);
 
+            if all {
+                datasets.into_values().dedup().collect_vec()
+            } else {
                 process_datasets(&headers, kind, &mut chunk, &mut route, query, datasets)?;
+                vec![route.file_path.clone()]
             }
-            Kind::Last => {
-                if !route.values_type.ends_with("Value") {
-                    route.file_path.set_extension(COMPRESSED_BIN_EXTENSION);
-                } else {
-                    route.file_path.set_extension(JSON_EXTENSION);
-                }
+        }
+        Kind::Last => {
+            if !route.values_type.ends_with("Value") {
+                route.file_path.set_extension(COMPRESSED_BIN_EXTENSION);
+            } else {
+                route.file_path.set_extension(JSON_EXTENSION);
             }
+
+            vec![route.file_path.clone()]
         }
-    }
+    };
 
-    let (date, response) = headers.check_if_modified_since(&route.file_path).unwrap();
+    let extension = Extension::from(&std::path::PathBuf::from(&path));
 
-    if let Some(response) = response {
+    let validators = Validators::compute(
+        &paths,
+        &format!(
+            "{}{}",
+            extension
+                .as_ref()
+                .map_or("", |extension| extension.to_str()),
+            serde_json::to_string(&chunk)?
+        ),
+        &cache,
+    )?;
+
+    if let Some(response) = headers.check_validators(&validators) {
         return Ok(response);
     }
 
     let type_name = route.values_type.split("::").last().unwrap();
 
-    let extension = Extension::from(&std::path::PathBuf::from(&path));
-
     let mut response = match type_name {
-        "u8" => typed_value_to_response::<u8>(kind, &route, chunk, id, extension)?,
-        "u16" => typed_value_to_response::<u16>(kind, &route, chunk, id, extension)?,
-        "u32" => typed_value_to_response::<u32>(kind, &route, chunk, id, extension)?,
-        "u64" => typed_value_to_response::<u64>(kind, &route, chunk, id, extension)?,
-        "usize" => typed_value_to_response::<usize>(kind, &route, chunk, id, extension)?,
-        "f32" => typed_value_to_response::<f32>(kind, &route, chunk, id, extension)?,
-        "f64" => typed_value_to_response::<f64>(kind, &route, chunk, id, extension)?,
-        "OHLC" => typed_value_to_response::<OHLC>(kind, &route, chunk, id, extension)?,
-        "Date" => typed_value_to_response::<Date>(kind, &route, chunk, id, extension)?,
-        "Height" => typed_value_to_response::<Height>(kind, &route, chunk, id, extension)?,
+        "u8" => {
+            typed_value_to_response::<u8>(kind, &route, chunk, id, extension, &cache, &validators)?
+        }
+        "u16" => {
+            typed_value_to_response::<u16>(kind, &route, chunk, id, extension, &cache, &validators)?
+        }
+        "u32" => {
+            typed_value_to_response::<u32>(kind, &route, chunk, id, extension, &cache, &validators)?
+        }
+        "u64" => {
+            typed_value_to_response::<u64>(kind, &route, chunk, id, extension, &cache, &validators)?
+        }
+        "usize" => typed_value_to_response::<usize>(
+            kind,
+            &route,
+            chunk,
+            id,
+            extension,
+            &cache,
+            &validators,
+        )?,
+        "f32" => {
+            typed_value_to_response::<f32>(kind, &route, chunk, id, extension, &cache, &validators)?
+        }
+        "f64" => {
+            typed_value_to_response::<f64>(kind, &route, chunk, id, extension, &cache, &validators)?
+        }
+        "OHLC" => typed_value_to_response::<OHLC>(
+            kind,
+            &route,
+            chunk,
+            id,
+            extension,
+            &cache,
+            &validators,
+        )?,
+        "Date" => typed_value_to_response::<Date>(
+            kind,
+            &route,
+            chunk,
+            id,
+            extension,
+            &cache,
+            &validators,
+        )?,
+        "Height" => typed_value_to_response::<Height>(
+            kind,
+            &route,
+            chunk,
+            id,
+            extension,
+            &cache,
+            &validators,
+        )?,
+        "Distribution" => typed_value_to_response::<Distribution>(
+            kind,
+            &route,
+            chunk,
+            id,
+            extension,
+            &cache,
+            &validators,
+        )?,
         "Value" => {
             value_to_response::<serde_json::Value>(Json::import(&route.file_path)?, extension)
         }
@@ -149,12 +224,12 @@ fn _dataset_handler(
     };
 
     let headers = response.headers_mut();
-    headers.insert_last_modified(date);
+    headers.insert_validators(&validators);
 
     Ok(response)
 }
 
-fn convert_path_to_id(s: &str) -> String {
+pub fn convert_path_to_id(s: &str) -> String {
     Extension::remove_extension(s).replace('-', "_")
 }
 
@@ -3,6 +3,10 @@ mod extension;
 mod fallback;
 
 mod response;
+mod stream;
+mod urpd;
 
 pub use dataset::*;
 pub use fallback::*;
+pub use stream::*;
+pub use urpd::*;
@@ -2,12 +2,15 @@ use std::fmt::Debug;
 
 use axum::response::{IntoResponse, Json, Response};
 use bincode::Decode;
-use parser::{Date, MapValue, SerializedBTreeMap, SerializedVec};
+use parser::{
+    Date, DateMap, DateMapChunkId, HeightMap, HeightMapChunkId, MapChunkId, MapValue,
+    Serialization, SerializedBTreeMap, SerializedVec,
+};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::{
-    api::structs::{Chunk, Kind, Route},
+    api::structs::{Cache, CacheStatus, Chunk, Kind, Route, Validators},
     header_map::HeaderMapUtils,
 };
 
@@ -37,19 +40,47 @@ pub fn typed_value_to_response<T>(
     chunk: Option<Chunk>,
     id: String,
     extension: Option<Extension>,
+    cache: &Cache,
+    validators: &Validators,
 ) -> color_eyre::Result<Response>
 where
-    T: Serialize + Debug + DeserializeOwned + Decode + MapValue,
+    T: Serialize + Debug + DeserializeOwned + Decode + MapValue + 'static,
 {
-    Ok(match kind {
+    let path = &route.file_path;
+    let content_hash = validators.content_hash;
+
+    // Every chunk is in the same columnar file
+    let cache_path = match chunk.as_ref() {
+        Some(chunk) if route.serialization == Serialization::Columnar => {
+            path.join(chunk.id.to_string())
+        }
+        _ => path.to_owned(),
+    };
+
+    let cache_status: CacheStatus;
+
+    let mut response = match kind {
         Kind::Date => {
-            let dataset = if chunk.is_some() {
-                route
-                    .serialization
-                    .import::<SerializedBTreeMap<Date, T>>(&route.file_path)?
-            } else {
-                SerializedBTreeMap::<Date, T>::import_all(&route.file_path, &route.serialization)
-            };
+            let dataset;
+            (dataset, cache_status) = cache.get_or_import(
+                &cache_path,
+                content_hash,
+                || {
+                    if let Some(chunk) = chunk.as_ref() {
+                        DateMap::<T>::_import_chunk(
+                            &DateMapChunkId::from_usize(chunk.id),
+                            path,
+                            &route.serialization,
+                        )
+                    } else {
+                        Ok(SerializedBTreeMap::<Date, T>::import_all(
+                            path,
+                            &route.serialization,
+                        ))
+                    }
+                },
+                |dataset| dataset.map.len() * size_of::<(Date, T)>(),
+            )?;
 
             if extension == Some(Extension::CSV) {
                 let mut csv = format!("date,{}\n", id);
@@ -60,17 +91,27 @@ where
 
                 string_to_response(csv, extension)
             } else {
-                dataset_to_response(dataset, chunk, extension)
+                dataset_to_response(&*dataset, chunk, extension)
             }
         }
         Kind::Height => {
-            let dataset = if chunk.is_some() {
-                route
-                    .serialization
-                    .import::<SerializedVec<T>>(&route.file_path)?
-            } else {
-                SerializedVec::<T>::import_all(&route.file_path, &route.serialization)
-            };
+            let dataset;
+            (dataset, cache_status) = cache.get_or_import(
+                &cache_path,
+                content_hash,
+                || {
+                    if let Some(chunk) = chunk.as_ref() {
+                        HeightMap::<T>::_import_chunk(
+                            &HeightMapChunkId::from_usize(chunk.id),
+                            path,
+                            &route.serialization,
+                        )
+                    } else {
+                        Ok(SerializedVec::<T>::import_all(path, &route.serialization))
+                    }
+                },
+                |dataset| dataset.map.len() * size_of::<T>(),
+            )?;
 
             if extension == Some(Extension::CSV) {
                 let mut csv = format!("height,{}\n", id);
@@ -83,11 +124,18 @@ where
 
                 string_to_response(csv, extension)
             } else {
-                dataset_to_response(dataset, chunk, extension)
+                dataset_to_response(&*dataset, chunk, extension)
             }
         }
         Kind::Last => {
-            let value = route.serialization.import::<T>(&route.file_path)?;
+            let value;
+            (value, cache_status) = cache.get_or_import(
+                path,
+                content_hash,
+                || route.serialization.import::<T>(path),
+                |_| size_of::<T>(),
+            )?;
+            let value = *value;
 
             if extension == Some(Extension::JSON) {
                 value_to_response(WrappedValue { value }, extension)
@@ -95,7 +143,11 @@ where
                 value_to_response(value, extension)
             }
         }
-    })
+    };
+
+    response.extensions_mut().insert(cache_status);
+
+    Ok(response)
 }
 
 pub fn string_to_response(s: String, extension: Option<Extension>) -> Response {
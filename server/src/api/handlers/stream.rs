@@ -0,0 +1,63 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::{Query, State},
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Response, Sse,
+    },
+};
+use color_eyre::owo_colors::OwoColorize;
+use parser::log;
+use serde::Deserialize;
+use tokio_stream::{wrappers::WatchStream, StreamExt};
+
+use crate::{header_map::HeaderMapUtils, AppState};
+
+use super::convert_path_to_id;
+
+#[derive(Deserialize)]
+pub struct StreamParams {
+    ids: Option<String>,
+}
+
+/// Server-sent events, one `update` event on connection and one after each export of the parser
+///
+/// Example: `/api/stream?ids=last-height,close`
+pub async fn stream_handler(
+    query: Query<StreamParams>,
+    State(app_state): State<AppState>,
+) -> Response {
+    log(&format!(
+        "stream{}",
+        query.ids.as_ref().map_or("".to_string(), |ids| format!(
+            "{}{ids}",
+            "?ids=".bright_black()
+        ))
+    ));
+
+    let ids = query.ids.as_ref().map_or(vec![], |ids| {
+        ids.split(',')
+            .map(|id| id.trim())
+            .filter(|id| !id.is_empty())
+            .map(convert_path_to_id)
+            .collect()
+    });
+
+    let stream = WatchStream::new(app_state.last_values.clone()).map(move |last_values| {
+        Ok::<_, Infallible>(
+            Event::default()
+                .event("update")
+                .json_data(last_values.to_update(&ids))
+                .unwrap(),
+        )
+    });
+
+    let mut response = Sse::new(stream)
+        .keep_alive(KeepAlive::default())
+        .into_response();
+
+    response.headers_mut().insert_cors();
+
+    response
+}
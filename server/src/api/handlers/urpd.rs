@@ -0,0 +1,104 @@
+use axum::{
+    extract::{Query, State},
+    response::{IntoResponse, Json, Response},
+};
+use chrono::NaiveDate;
+use color_eyre::owo_colors::OwoColorize;
+use reqwest::StatusCode;
+use serde::{Deserialize, Serialize};
+
+use parser::{log, Date, DateMap, Distribution};
+
+use crate::{header_map::HeaderMapUtils, AppState};
+
+#[derive(Deserialize)]
+pub struct UrpdParams {
+    cohort: String,
+    date: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Urpd {
+    cohort: String,
+    date: Date,
+    /// (lowest price, highest price, supply)
+    bins: Vec<(f64, f64, f32)>,
+}
+
+/// UTXO realized price distribution of a cohort at the end of a day, the last one by default
+///
+/// Example: `/api/urpd?cohort=sth&date=2024-04-20`, `cohort=all` for the whole supply
+pub async fn urpd_handler(query: Query<UrpdParams>, State(app_state): State<AppState>) -> Response {
+    match _urpd_handler(query, app_state) {
+        Ok(response) => response,
+        Err((status, error)) => {
+            let mut response = (status, error).into_response();
+
+            response.headers_mut().insert_cors();
+
+            response
+        }
+    }
+}
+
+fn _urpd_handler(
+    Query(UrpdParams { cohort, date }): Query<UrpdParams>,
+    AppState { routes, .. }: AppState,
+) -> Result<Response, (StatusCode, String)> {
+    log(&format!(
+        "urpd{}{cohort}{}",
+        "?cohort=".bright_black(),
+        date.as_ref().map_or("".to_string(), |date| format!(
+            "{}{date}",
+            "&date=".bright_black()
+        ))
+    ));
+
+    let not_found = |message: &str| (StatusCode::NOT_FOUND, message.to_owned());
+
+    // The dataset of the whole supply isn't prefixed by a cohort name
+    let key = if cohort == "all" {
+        "urpd".to_owned()
+    } else {
+        format!("{cohort}_urpd")
+    };
+
+    let route = routes
+        .date
+        .get(&key)
+        .ok_or_else(|| not_found("Unknown cohort"))?;
+
+    let date = date
+        .map(|date| {
+            NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                .map(Date::wrap)
+                .map_err(|_| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        "Invalid date, expected YYYY-MM-DD".to_owned(),
+                    )
+                })
+        })
+        .transpose()?;
+
+    let (date, distribution) = match date {
+        Some(date) => {
+            DateMap::<Distribution>::_import_key(&route.file_path, &route.serialization, &date)
+                .map(|distribution| distribution.map(|distribution| (date, distribution)))
+        }
+        None => DateMap::<Distribution>::_import_last(&route.file_path, &route.serialization),
+    }
+    .map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()))?
+    .ok_or_else(|| not_found("Couldn't find date"))?;
+
+    let mut response = Json(Urpd {
+        cohort,
+        date,
+        bins: distribution.bins(),
+    })
+    .into_response();
+
+    response.headers_mut().insert_cors();
+
+    Ok(response)
+}
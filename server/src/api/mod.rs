@@ -1,5 +1,5 @@
 use axum::{routing::get, Router};
-use handlers::{dataset_handler, fallback};
+use handlers::{dataset_handler, fallback, stream_handler, urpd_handler};
 
 use crate::AppState;
 
@@ -12,7 +12,9 @@ pub trait ApiRoutes {
 
 impl ApiRoutes for Router<AppState> {
     fn add_api_routes(self) -> Self {
-        self.route("/api/*path", get(dataset_handler))
+        self.route("/api/stream", get(stream_handler))
+            .route("/api/urpd", get(urpd_handler))
+            .route("/api/*path", get(dataset_handler))
             .route("/api/", get(fallback))
             .route("/api", get(fallback))
     }
@@ -0,0 +1,174 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    fs,
+    hash::Hasher,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use lru::LruCache;
+use tokio::sync::watch;
+
+use super::StableHasher;
+
+type Decoded = Arc<dyn Any + Send + Sync>;
+
+struct FileHash {
+    modified: SystemTime,
+    len: u64,
+    hash: u64,
+}
+
+struct DecodedCache {
+    entries: LruCache<(PathBuf, u64), (Decoded, u64)>,
+    /// Sum of the decoded sizes of the entries
+    size: u64,
+    capacity: u64,
+}
+
+/// In memory cache of decoded chunks, keyed on their path and the hash of their content
+///
+/// Bounded by the decoded size of the chunks, emptied after each export of the parser
+pub struct Cache {
+    decoded: Mutex<DecodedCache>,
+    hashes: Mutex<HashMap<PathBuf, FileHash>>,
+}
+
+/// In megabytes
+pub const DEFAULT_CACHE_CAPACITY: usize = 512;
+
+const BYTES_IN_MB: u64 = 1_000_000;
+
+/// Files hashed since the last clear, one entry per file of the datasets at most
+const MAX_FILE_HASHES: usize = 100_000;
+
+/// Inserted in the extensions of responses built from the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+impl DecodedCache {
+    /// Values bigger than the whole cache aren't kept
+    fn put(&mut self, key: (PathBuf, u64), value: Decoded, size: u64) {
+        if size > self.capacity {
+            return;
+        }
+
+        if let Some((_, (_, previous_size))) = self.entries.push(key, (value, size)) {
+            self.size -= previous_size;
+        }
+
+        self.size += size;
+
+        while self.size > self.capacity {
+            let Some((_, (_, evicted_size))) = self.entries.pop_lru() else {
+                break;
+            };
+
+            self.size -= evicted_size;
+        }
+    }
+}
+
+impl Cache {
+    /// `capacity` is in megabytes
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            decoded: Mutex::new(DecodedCache {
+                entries: LruCache::unbounded(),
+                size: 0,
+                capacity: capacity as u64 * BYTES_IN_MB,
+            }),
+            hashes: Mutex::default(),
+        }
+    }
+
+    /// Returns the modification time, the length and the hash of the content of a file, only reading it if it changed since the last call
+    pub fn hash_file(&self, path: &Path) -> color_eyre::Result<(SystemTime, u64, u64)> {
+        let metadata = path.metadata()?;
+        let modified = metadata.modified()?;
+        let len = metadata.len();
+
+        if let Some(file_hash) = self.hashes.lock().unwrap().get(path) {
+            if file_hash.modified == modified && file_hash.len == len {
+                return Ok((modified, len, file_hash.hash));
+            }
+        }
+
+        let mut hasher = StableHasher::default();
+        hasher.write(&fs::read(path)?);
+        let hash = hasher.finish();
+
+        let mut hashes = self.hashes.lock().unwrap();
+
+        if hashes.len() >= MAX_FILE_HASHES {
+            hashes.clear();
+        }
+
+        hashes.insert(
+            path.to_owned(),
+            FileHash {
+                modified,
+                len,
+                hash,
+            },
+        );
+
+        Ok((modified, len, hash))
+    }
+
+    /// `decoded_size` returns the size in memory of the value, used to bound the cache
+    pub fn get_or_import<T>(
+        &self,
+        path: &Path,
+        content_hash: u64,
+        import: impl FnOnce() -> color_eyre::Result<T>,
+        decoded_size: impl FnOnce(&T) -> usize,
+    ) -> color_eyre::Result<(Arc<T>, CacheStatus)>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = (path.to_owned(), content_hash);
+
+        if let Some((decoded, _)) = self.decoded.lock().unwrap().entries.get(&key) {
+            if let Ok(value) = decoded.clone().downcast::<T>() {
+                return Ok((value, CacheStatus::Hit));
+            }
+        }
+
+        // Not holding the lock while decoding
+        let value = Arc::new(import()?);
+
+        let size = decoded_size(&value) as u64;
+
+        self.decoded.lock().unwrap().put(key, value.clone(), size);
+
+        Ok((value, CacheStatus::Miss))
+    }
+
+    pub fn clear(&self) {
+        let mut decoded = self.decoded.lock().unwrap();
+        decoded.entries.clear();
+        decoded.size = 0;
+        drop(decoded);
+
+        self.hashes.lock().unwrap().clear();
+    }
+
+    pub fn clear_on_change<T>(self: &Arc<Self>, mut receiver: watch::Receiver<T>)
+    where
+        T: Send + Sync + 'static,
+    {
+        let cache = self.clone();
+
+        tokio::spawn(async move {
+            while receiver.changed().await.is_ok() {
+                cache.clear();
+            }
+        });
+    }
+}
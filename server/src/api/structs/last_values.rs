@@ -0,0 +1,97 @@
+use std::{
+    collections::BTreeMap,
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use derive_deref::{Deref, DerefMut};
+use parser::{log, Json};
+use serde::Serialize;
+use serde_json::Value;
+use tokio::sync::watch;
+
+/// Written by the parser in the datasets folder at the end of every export, used as the notification file
+const LAST_VALUES_FILE_NAME: &str = "last.json";
+const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+const HEIGHT_KEY: &str = "last-height";
+const DATE_KEY: &str = "date";
+
+#[derive(Clone, Debug, Default, Deref, DerefMut)]
+pub struct LastValues(BTreeMap<String, Value>);
+
+#[derive(Debug, Serialize)]
+pub struct LastValuesUpdate {
+    pub height: Option<Value>,
+    pub date: Option<Value>,
+    pub values: BTreeMap<String, Value>,
+}
+
+impl LastValues {
+    pub fn import(path: &Path) -> color_eyre::Result<Self> {
+        Ok(Self(Json::import(path)?))
+    }
+
+    /// Ids are the ones used by the api (`last_height`), keys in `last.json` are dash joined (`last-height`)
+    pub fn to_update(&self, ids: &[String]) -> LastValuesUpdate {
+        LastValuesUpdate {
+            height: self.get(HEIGHT_KEY).cloned(),
+            date: self.get(DATE_KEY).cloned(),
+            values: ids
+                .iter()
+                .filter_map(|id| {
+                    self.get(&id.replace('_', "-"))
+                        .map(|value| (id.to_owned(), value.clone()))
+                })
+                .collect(),
+        }
+    }
+
+    /// Polls the modification time of `last.json` and publishes its content after each export
+    pub fn watch(datasets_path: &Path) -> watch::Receiver<Arc<Self>> {
+        let path = datasets_path.join(LAST_VALUES_FILE_NAME);
+
+        let (sender, receiver) = watch::channel(Arc::new(Self::import(&path).unwrap_or_default()));
+
+        tokio::spawn(async move {
+            let mut last_modified = Self::modified(&path);
+
+            let mut interval = tokio::time::interval(WATCH_INTERVAL);
+
+            loop {
+                interval.tick().await;
+
+                if sender.is_closed() {
+                    break;
+                }
+
+                let modified = Self::modified(&path);
+
+                if modified.is_none() || modified == last_modified {
+                    continue;
+                }
+
+                // The parser might still be writing, only move forward once the file is readable
+                match Self::import(&path) {
+                    Ok(last_values) => {
+                        last_modified = modified;
+
+                        log("New export detected");
+
+                        sender.send_replace(Arc::new(last_values));
+                    }
+                    Err(_) => continue,
+                }
+            }
+        });
+
+        receiver
+    }
+
+    fn modified(path: &Path) -> Option<SystemTime> {
+        path.metadata()
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+}
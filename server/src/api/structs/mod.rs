@@ -1,9 +1,17 @@
+mod cache;
 mod chunk;
 mod kind;
+mod last_values;
 mod paths;
 mod routes;
+mod stable_hasher;
+mod validators;
 
+pub use cache::*;
 pub use chunk::*;
 pub use kind::*;
+pub use last_values::*;
 pub use paths::*;
 pub use routes::*;
+pub use stable_hasher::*;
+pub use validators::*;
@@ -1,14 +1,17 @@
 use std::{
     collections::{BTreeMap, HashMap},
     fs,
-    path::{Path, PathBuf},
+    path::PathBuf,
 };
 
 use derive_deref::{Deref, DerefMut};
 use itertools::Itertools;
-use parser::{Json, Serialization};
+use parser::{log, Json, Serialization, COLUMNAR_FILE_NAME};
 
-use crate::Grouped;
+use crate::{
+    config::{DEFAULT_DATASETS_PATH, DEFAULT_PRICE_PATH},
+    Config, Grouped,
+};
 
 use super::Paths;
 
@@ -23,13 +26,15 @@ pub struct Route {
 #[derive(Clone, Default, Deref, DerefMut)]
 pub struct Routes(pub Grouped<HashMap<String, Route>>);
 
-const INPUTS_PATH: &str = "./in";
-const WEBSITE_TYPES_PATH: &str = "../website/scripts/types";
+const WEBSITE_TYPES_PATH: &str = "scripts/types";
 
 impl Routes {
-    pub fn build() -> Self {
+    pub fn build(config: &Config) -> Self {
         let path_to_type: BTreeMap<String, String> =
-            Json::import(Path::new(&format!("{INPUTS_PATH}/disk_path_to_type.json"))).unwrap();
+            Json::import(&config.inputs_path().join("disk_path_to_type.json")).unwrap();
+
+        let datasets_path = config.datasets_path();
+        let price_path = config.price_path();
 
         let mut routes = Routes::default();
 
@@ -57,9 +62,22 @@ impl Routes {
 
             let url_path = split_key.iter().join("-");
 
+            // Paths are relative to the parser, move them to the configured directories
             let file_path = PathBuf::from(key.to_owned());
+            let file_path = if let Ok(path) = file_path.strip_prefix(DEFAULT_DATASETS_PATH) {
+                datasets_path.join(path)
+            } else if let Ok(path) = file_path.strip_prefix(DEFAULT_PRICE_PATH) {
+                price_path.join(path)
+            } else {
+                file_path
+            };
             let values_type = value.to_owned();
 
+            // Maps stored with the columnar feature of the parser
+            if file_path.join(COLUMNAR_FILE_NAME).exists() {
+                serialization = Serialization::Columnar;
+            }
+
             if last == "date" {
                 routes.date.insert(
                     map_key,
@@ -99,7 +117,7 @@ impl Routes {
         routes
     }
 
-    pub fn generate_dts_file(&self) {
+    pub fn generate_dts_file(&self, config: &Config) {
         let map_to_type = |name: &str, map: &HashMap<String, Route>| -> String {
             let paths = map
                 .values()
@@ -115,11 +133,12 @@ impl Routes {
 
         let last_type = map_to_type("Last", &self.last);
 
-        fs::write(
-            format!("{WEBSITE_TYPES_PATH}/paths.d.ts"),
+        if let Err(error) = fs::write(
+            config.website_path().join(WEBSITE_TYPES_PATH).join("paths.d.ts"),
             format!("// This file is auto generated by the server\n// Manual changes are forbidden\n\n{date_type}\n{height_type}\n{last_type}"),
-        )
-        .unwrap();
+        ) {
+            log(&format!("Couldn't generate paths.d.ts: {error}"));
+        }
     }
 
     pub fn to_full_paths(&self, host: String) -> Paths {
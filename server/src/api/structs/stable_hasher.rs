@@ -0,0 +1,28 @@
+use std::hash::Hasher;
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// FNV-1a hasher, unlike `DefaultHasher` its output doesn't change between builds or Rust versions
+///
+/// Only feed it bytes with `write`, the `Hash` implementations of std types aren't guaranteed to be stable either
+pub struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self(FNV_OFFSET_BASIS)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|byte| {
+            self.0 ^= *byte as u64;
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        });
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
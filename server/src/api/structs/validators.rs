@@ -0,0 +1,51 @@
+use std::{hash::Hasher, path::PathBuf};
+
+use chrono::{DateTime, Timelike, Utc};
+
+use super::{Cache, StableHasher};
+
+/// Cache validators of a response built from one or many files
+#[derive(Debug, Clone)]
+pub struct Validators {
+    pub etag: String,
+    pub last_modified: DateTime<Utc>,
+    pub content_hash: u64,
+}
+
+impl Validators {
+    /// The etag is a hash of the content of every file in order plus the representation (extension, chunk links, ...)
+    /// and the last modified date is the most recent one of all files
+    ///
+    /// The etag is weak since the same one is sent for the identity, gzip and br bodies of the compression layer
+    pub fn compute(
+        paths: &[PathBuf],
+        representation: &str,
+        cache: &Cache,
+    ) -> color_eyre::Result<Self> {
+        let mut hasher = StableHasher::default();
+
+        let mut last_modified = DateTime::<Utc>::MIN_UTC;
+
+        paths
+            .iter()
+            .try_for_each(|path| -> color_eyre::Result<()> {
+                let (modified, _, hash) = cache.hash_file(path)?;
+
+                last_modified = last_modified.max(modified.into());
+
+                hasher.write(&hash.to_le_bytes());
+
+                Ok(())
+            })?;
+
+        let content_hash = hasher.finish();
+
+        hasher.write(representation.as_bytes());
+
+        Ok(Self {
+            etag: format!("W/\"{:016x}\"", hasher.finish()),
+            last_modified: last_modified.with_nanosecond(0).unwrap(),
+            content_hash,
+        })
+    }
+}
@@ -0,0 +1,249 @@
+use std::{fs, path::PathBuf};
+
+use clap::Parser;
+use color_eyre::eyre::eyre;
+use parser::log;
+use serde::{Deserialize, Serialize};
+
+use crate::api::structs::DEFAULT_CACHE_CAPACITY;
+
+const DEFAULT_ADDRESS: &str = "0.0.0.0";
+pub const DEFAULT_PORT: u16 = 3110;
+const DEFAULT_INPUTS_PATH: &str = "./in";
+pub const DEFAULT_DATASETS_PATH: &str = "../datasets";
+pub const DEFAULT_PRICE_PATH: &str = "../price";
+const DEFAULT_WEBSITE_PATH: &str = "../website";
+
+#[derive(Parser, Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[command(version, about, long_about = None)]
+pub struct Config {
+    /// Address to bind to, default: 0.0.0.0, saved
+    #[arg(long, value_name = "IP")]
+    pub address: Option<String>,
+
+    /// Port to bind to, fails if it's already taken, default: first available starting from 3110, saved
+    #[arg(long, value_name = "PORT")]
+    pub port: Option<u16>,
+
+    /// Inputs directory path generated by the parser, default: ./in, saved
+    #[arg(long, value_name = "DIR")]
+    pub inputs: Option<String>,
+
+    /// Datasets directory path, default: ../datasets, saved
+    #[arg(long, value_name = "DIR")]
+    pub datasets: Option<String>,
+
+    /// Price datasets directory path, default: ../price, saved
+    #[arg(long, value_name = "DIR")]
+    pub price: Option<String>,
+
+    /// Website directory path, default: ../website, saved
+    #[arg(long, value_name = "DIR")]
+    pub website: Option<String>,
+
+    /// Serve the website, default: true, saved
+    #[arg(long, value_name = "BOOL")]
+    pub serve_website: Option<bool>,
+
+    /// Allowed CORS origins separated by commas, default: *, saved
+    #[arg(long, value_name = "ORIGINS")]
+    pub origins: Option<String>,
+
+    /// Maximum size in megabytes of the decoded chunks kept in memory, measured by their decoded size, default: 512, saved
+    #[arg(long, value_name = "MB")]
+    pub cache_capacity: Option<usize>,
+
+    /// Requests per second allowed per IP, requests with `all=true` count for more, default: unlimited, saved
+    #[arg(long, value_name = "REQUESTS")]
+    pub rate_limit: Option<f64>,
+
+    /// Maximum burst of requests per IP or API key, default: 100, saved
+    #[arg(long, value_name = "REQUESTS")]
+    pub rate_limit_burst: Option<f64>,
+
+    /// API keys separated by commas, sent in the X-API-Key header to be rate limited by key instead of IP, saved
+    #[arg(long, value_name = "KEYS")]
+    pub api_keys: Option<String>,
+
+    /// Requests per second allowed per API key, default: unlimited, saved
+    #[arg(long, value_name = "REQUESTS")]
+    pub api_key_rate_limit: Option<f64>,
+}
+
+impl Config {
+    const PATH: &'static str = "./config.toml";
+
+    pub fn import() -> color_eyre::Result<Self> {
+        let mut config_saved = fs::read_to_string(Self::PATH)
+            .map_or(Config::default(), |contents| {
+                toml::from_str(&contents).unwrap_or_default()
+            });
+
+        let mut config_args = Config::parse();
+
+        if let Some(address) = config_args.address.take() {
+            config_saved.address = Some(address);
+        }
+
+        if let Some(port) = config_args.port.take() {
+            config_saved.port = Some(port);
+        }
+
+        if let Some(inputs) = config_args.inputs.take() {
+            config_saved.inputs = Some(inputs);
+        }
+
+        if let Some(datasets) = config_args.datasets.take() {
+            config_saved.datasets = Some(datasets);
+        }
+
+        if let Some(price) = config_args.price.take() {
+            config_saved.price = Some(price);
+        }
+
+        if let Some(website) = config_args.website.take() {
+            config_saved.website = Some(website);
+        }
+
+        if let Some(serve_website) = config_args.serve_website.take() {
+            config_saved.serve_website = Some(serve_website);
+        }
+
+        if let Some(origins) = config_args.origins.take() {
+            config_saved.origins = Some(origins);
+        }
+
+        if let Some(cache_capacity) = config_args.cache_capacity.take() {
+            config_saved.cache_capacity = Some(cache_capacity);
+        }
+
+        if let Some(rate_limit) = config_args.rate_limit.take() {
+            config_saved.rate_limit = Some(rate_limit);
+        }
+
+        if let Some(rate_limit_burst) = config_args.rate_limit_burst.take() {
+            config_saved.rate_limit_burst = Some(rate_limit_burst);
+        }
+
+        if let Some(api_keys) = config_args.api_keys.take() {
+            config_saved.api_keys = Some(api_keys);
+        }
+
+        if let Some(api_key_rate_limit) = config_args.api_key_rate_limit.take() {
+            config_saved.api_key_rate_limit = Some(api_key_rate_limit);
+        }
+
+        // Done importing
+
+        let config = config_saved;
+
+        config.check()?;
+
+        // Might be running with a read-only layout
+        if let Err(error) = config.write() {
+            log(&format!("Couldn't save the configuration: {error}"));
+        }
+
+        log("---");
+        log("Configuration:");
+        log(&format!("address: {:?}", config.address));
+        log(&format!("port: {:?}", config.port));
+        log(&format!("inputs: {:?}", config.inputs));
+        log(&format!("datasets: {:?}", config.datasets));
+        log(&format!("price: {:?}", config.price));
+        log(&format!("website: {:?}", config.website));
+        log(&format!("serve_website: {:?}", config.serve_website));
+        log(&format!("origins: {:?}", config.origins));
+        log(&format!("cache_capacity: {:?}", config.cache_capacity));
+        log(&format!("rate_limit: {:?}", config.rate_limit));
+        log(&format!("rate_limit_burst: {:?}", config.rate_limit_burst));
+        log(&format!(
+            "api_keys: {:?}",
+            config.api_keys.as_ref().map(|_| "***")
+        ));
+        log(&format!(
+            "api_key_rate_limit: {:?}",
+            config.api_key_rate_limit
+        ));
+        log("---");
+
+        if config_args != Config::default() {
+            dbg!(config_args);
+            panic!("Didn't consume the full config")
+        }
+
+        Ok(config)
+    }
+
+    /// A rate of 0 would never refill the buckets
+    fn check(&self) -> color_eyre::Result<()> {
+        [
+            ("rate_limit", self.rate_limit),
+            ("rate_limit_burst", self.rate_limit_burst),
+            ("api_key_rate_limit", self.api_key_rate_limit),
+        ]
+        .into_iter()
+        .try_for_each(|(name, value)| match value {
+            Some(value) if !(value.is_finite() && value > 0.0) => {
+                Err(eyre!("{name} must be a positive number, got {value}"))
+            }
+            _ => Ok(()),
+        })
+    }
+
+    fn write(&self) -> std::io::Result<()> {
+        fs::write(Self::PATH, toml::to_string(self).unwrap())
+    }
+
+    pub fn address(&self) -> &str {
+        self.address.as_deref().unwrap_or(DEFAULT_ADDRESS)
+    }
+
+    pub fn inputs_path(&self) -> PathBuf {
+        PathBuf::from(self.inputs.as_deref().unwrap_or(DEFAULT_INPUTS_PATH))
+    }
+
+    pub fn datasets_path(&self) -> PathBuf {
+        PathBuf::from(self.datasets.as_deref().unwrap_or(DEFAULT_DATASETS_PATH))
+    }
+
+    pub fn price_path(&self) -> PathBuf {
+        PathBuf::from(self.price.as_deref().unwrap_or(DEFAULT_PRICE_PATH))
+    }
+
+    pub fn website_path(&self) -> PathBuf {
+        PathBuf::from(self.website.as_deref().unwrap_or(DEFAULT_WEBSITE_PATH))
+    }
+
+    pub fn serve_website(&self) -> bool {
+        self.serve_website.unwrap_or(true)
+    }
+
+    /// `None` if every origin is allowed
+    pub fn origins(&self) -> Option<Vec<String>> {
+        self.origins
+            .as_ref()
+            .map(|origins| {
+                origins
+                    .split(',')
+                    .map(|origin| origin.trim().to_owned())
+                    .filter(|origin| !origin.is_empty())
+                    .collect::<Vec<_>>()
+            })
+            .filter(|origins| !origins.iter().any(|origin| origin == "*"))
+    }
+
+    pub fn api_keys(&self) -> Vec<String> {
+        self.api_keys.as_ref().map_or(vec![], |api_keys| {
+            api_keys
+                .split(',')
+                .map(|api_key| api_key.trim().to_owned())
+                .filter(|api_key| !api_key.is_empty())
+                .collect()
+        })
+    }
+
+    pub fn cache_capacity(&self) -> usize {
+        self.cache_capacity.unwrap_or(DEFAULT_CACHE_CAPACITY)
+    }
+}
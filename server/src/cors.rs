@@ -0,0 +1,36 @@
+use axum::{
+    extract::{Request, State},
+    http::header,
+    middleware::Next,
+    response::Response,
+};
+
+use crate::AppState;
+
+/// Restricts `Access-Control-Allow-Origin` to the configured origins, responses allow any origin otherwise
+pub async fn cors_middleware(
+    State(app_state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let origin = request.headers().get(header::ORIGIN).cloned();
+
+    let mut response = next.run(request).await;
+
+    if let Some(origins) = app_state.config.origins() {
+        let headers = response.headers_mut();
+
+        headers.remove(header::ACCESS_CONTROL_ALLOW_ORIGIN);
+        headers.append(header::VARY, "Origin".parse().unwrap());
+
+        if let Some(origin) = origin.filter(|origin| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| origins.iter().any(|allowed| allowed == origin))
+        }) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+        }
+    }
+
+    response
+}
@@ -8,10 +8,12 @@ use axum::{
 use chrono::{DateTime, Timelike, Utc};
 use parser::log;
 use reqwest::{
-    header::{HOST, IF_MODIFIED_SINCE},
+    header::{HOST, IF_MODIFIED_SINCE, IF_NONE_MATCH},
     StatusCode,
 };
 
+use crate::api::structs::Validators;
+
 const STALE_IF_ERROR: u64 = 30_000_000; // 1 Year ish
 const MODIFIED_SINCE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
 
@@ -30,9 +32,14 @@ pub trait HeaderMapUtils {
         path: &Path,
     ) -> color_eyre::Result<(DateTime<Utc>, Option<Response<Body>>)>;
 
+    fn get_if_none_match(&self) -> Option<&str>;
+    fn check_validators(&self, validators: &Validators) -> Option<Response<Body>>;
+
     fn insert_cache_control_immutable(&mut self);
     fn insert_cache_control_revalidate(&mut self, max_age: u64, stale_while_revalidate: u64);
     fn insert_last_modified(&mut self, date: DateTime<Utc>);
+    fn insert_etag(&mut self, etag: &str);
+    fn insert_validators(&mut self, validators: &Validators);
 
     fn insert_content_disposition_attachment(&mut self);
 
@@ -110,6 +117,15 @@ impl HeaderMapUtils for HeaderMap {
         self.insert(header::LAST_MODIFIED, formatted.parse().unwrap());
     }
 
+    fn insert_etag(&mut self, etag: &str) {
+        self.insert(header::ETAG, etag.parse().unwrap());
+    }
+
+    fn insert_validators(&mut self, validators: &Validators) {
+        self.insert_etag(&validators.etag);
+        self.insert_last_modified(validators.last_modified);
+    }
+
     fn check_if_modified_since(
         &self,
         path: &Path,
@@ -131,6 +147,39 @@ impl HeaderMapUtils for HeaderMap {
         Ok((date, response_opt))
     }
 
+    fn get_if_none_match(&self) -> Option<&str> {
+        self.get(IF_NONE_MATCH)
+            .and_then(|if_none_match| if_none_match.to_str().ok())
+    }
+
+    // https://www.rfc-editor.org/rfc/rfc9110#section-13.2.2
+    fn check_validators(&self, validators: &Validators) -> Option<Response<Body>> {
+        let not_modified = if let Some(if_none_match) = self.get_if_none_match() {
+            if_none_match
+                .split(',')
+                .map(|etag| etag.trim())
+                .any(|etag| {
+                    // Weak comparison
+                    etag == "*"
+                        || etag.trim_start_matches("W/") == validators.etag.trim_start_matches("W/")
+                })
+        } else {
+            self.get_if_modified_since()
+                .is_some_and(|if_modified_since| validators.last_modified <= if_modified_since)
+        };
+
+        if !not_modified {
+            return None;
+        }
+
+        let mut response = (StatusCode::NOT_MODIFIED, "").into_response();
+        let headers = response.headers_mut();
+        headers.insert_cors();
+        headers.insert_validators(validators);
+
+        Some(response)
+    }
+
     fn get_if_modified_since(&self) -> Option<DateTime<Utc>> {
         if let Some(modified_since) = self.get(IF_MODIFIED_SINCE) {
             if let Ok(modified_since) = modified_since.to_str() {
@@ -1,15 +1,26 @@
-use std::sync::Arc;
-
-use api::{structs::Routes, ApiRoutes};
-use axum::{serve, Router};
+use std::{net::SocketAddr, sync::Arc};
+
+use api::{
+    structs::{Cache, LastValues, Routes},
+    ApiRoutes,
+};
+use axum::{middleware, routing::get, serve, Router};
+use config::{Config, DEFAULT_PORT};
+use cors::cors_middleware;
+use metrics::{metrics_handler, metrics_middleware, Metrics};
 use parser::{log, reset_logs};
+use rate_limit::{rate_limit_middleware, RateLimiter};
 use serde::Serialize;
-use tokio::net::TcpListener;
+use tokio::{net::TcpListener, sync::watch};
 use tower_http::compression::CompressionLayer;
 use website::WebsiteRoutes;
 
 mod api;
+mod config;
+mod cors;
 mod header_map;
+mod metrics;
+mod rate_limit;
 mod website;
 
 #[derive(Clone, Debug, Default, Serialize)]
@@ -21,7 +32,12 @@ pub struct Grouped<T> {
 
 #[derive(Clone)]
 pub struct AppState {
+    config: Arc<Config>,
     routes: Arc<Routes>,
+    last_values: watch::Receiver<Arc<LastValues>>,
+    cache: Arc<Cache>,
+    rate_limiter: Arc<RateLimiter>,
+    metrics: Arc<Metrics>,
 }
 
 #[tokio::main]
@@ -30,12 +46,29 @@ async fn main() -> color_eyre::Result<()> {
 
     reset_logs();
 
-    let routes = Routes::build();
+    let config = Config::import()?;
+
+    let routes = Routes::build(&config);
+
+    if config.serve_website() {
+        routes.generate_dts_file(&config);
+    }
 
-    routes.generate_dts_file();
+    let last_values = LastValues::watch(&config.datasets_path());
+
+    let cache = Arc::new(Cache::new(config.cache_capacity()));
+    cache.clear_on_change(last_values.clone());
+
+    let rate_limiter = Arc::new(RateLimiter::default());
+    rate_limiter.prune_periodically();
 
     let state = AppState {
+        config: Arc::new(config.clone()),
         routes: Arc::new(routes),
+        last_values,
+        cache,
+        rate_limiter,
+        metrics: Arc::default(),
     };
 
     let compression_layer = CompressionLayer::new()
@@ -44,27 +77,56 @@ async fn main() -> color_eyre::Result<()> {
         .gzip(true)
         .zstd(true);
 
-    let router = Router::new()
+    let mut router = Router::new()
         .add_api_routes()
-        .add_website_routes()
+        .route("/metrics", get(metrics_handler));
+
+    if config.serve_website() {
+        router = router.add_website_routes();
+    }
+
+    let router = router
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            rate_limit_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            cors_middleware,
+        ))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            metrics_middleware,
+        ))
         .with_state(state)
         .layer(compression_layer);
 
-    let mut port = 3110;
+    let address = config.address();
+
+    let listener = if let Some(port) = config.port {
+        TcpListener::bind(format!("{address}:{port}")).await?
+    } else {
+        let mut port = DEFAULT_PORT;
 
-    let mut listener;
-    loop {
-        listener = TcpListener::bind(format!("0.0.0.0:{port}")).await;
-        if listener.is_ok() {
-            break;
+        let mut listener;
+        loop {
+            listener = TcpListener::bind(format!("{address}:{port}")).await;
+            if listener.is_ok() {
+                break;
+            }
+            port += 1;
         }
-        port += 1;
-    }
 
-    log(&format!("Starting server on port {port}..."));
-    let listener = listener.unwrap();
+        listener.unwrap()
+    };
+
+    log(&format!("Starting server on {}...", listener.local_addr()?));
 
-    serve(listener, router).await?;
+    serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
     Ok(())
 }
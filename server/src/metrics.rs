@@ -0,0 +1,259 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::HttpBody,
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use reqwest::header;
+
+use crate::{api::structs::CacheStatus, AppState};
+
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+#[derive(Default)]
+struct RouteMetrics {
+    status_to_requests: BTreeMap<u16, u64>,
+    duration_sum: f64,
+    duration_count: u64,
+    bytes: u64,
+    cache_hits: u64,
+    cache_misses: u64,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    routes: BTreeMap<String, RouteMetrics>,
+    // One more for +Inf
+    latency_buckets: [u64; LATENCY_BUCKETS.len() + 1],
+    latency_sum: f64,
+    latency_count: u64,
+}
+
+/// Request counts, latencies, bytes served (before compression) and cache hits per route, in the Prometheus text format
+#[derive(Default)]
+pub struct Metrics(Mutex<MetricsState>);
+
+impl Metrics {
+    fn record(
+        &self,
+        route: String,
+        status: u16,
+        duration: Duration,
+        bytes: u64,
+        cache_status: Option<CacheStatus>,
+    ) {
+        let seconds = duration.as_secs_f64();
+
+        let mut state = self.0.lock().unwrap();
+
+        let bucket = LATENCY_BUCKETS
+            .iter()
+            .position(|bound| seconds <= *bound)
+            .unwrap_or(LATENCY_BUCKETS.len());
+        state.latency_buckets[bucket] += 1;
+        state.latency_sum += seconds;
+        state.latency_count += 1;
+
+        let route = state.routes.entry(route).or_default();
+
+        *route.status_to_requests.entry(status).or_default() += 1;
+        route.duration_sum += seconds;
+        route.duration_count += 1;
+        route.bytes += bytes;
+
+        match cache_status {
+            Some(CacheStatus::Hit) => route.cache_hits += 1,
+            Some(CacheStatus::Miss) => route.cache_misses += 1,
+            None => {}
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let state = self.0.lock().unwrap();
+
+        let mut s = String::new();
+
+        write_header(
+            &mut s,
+            "kibo_requests_total",
+            "counter",
+            "Number of requests by route and status",
+        );
+        state.routes.iter().for_each(|(route, metrics)| {
+            metrics
+                .status_to_requests
+                .iter()
+                .for_each(|(status, count)| {
+                    writeln!(
+                        s,
+                        "kibo_requests_total{{route=\"{}\",status=\"{status}\"}} {count}",
+                        escape(route)
+                    )
+                    .unwrap();
+                });
+        });
+
+        write_header(
+            &mut s,
+            "kibo_request_duration_seconds",
+            "summary",
+            "Time spent handling requests by route",
+        );
+        state.routes.iter().for_each(|(route, metrics)| {
+            let route = escape(route);
+            writeln!(
+                s,
+                "kibo_request_duration_seconds_sum{{route=\"{route}\"}} {}",
+                metrics.duration_sum
+            )
+            .unwrap();
+            writeln!(
+                s,
+                "kibo_request_duration_seconds_count{{route=\"{route}\"}} {}",
+                metrics.duration_count
+            )
+            .unwrap();
+        });
+
+        write_header(
+            &mut s,
+            "kibo_request_latency_seconds",
+            "histogram",
+            "Time spent handling requests",
+        );
+        let mut cumulative = 0;
+        LATENCY_BUCKETS
+            .iter()
+            .map(|bound| bound.to_string())
+            .chain(["+Inf".to_string()])
+            .zip(state.latency_buckets.iter())
+            .for_each(|(bound, count)| {
+                cumulative += count;
+                writeln!(
+                    s,
+                    "kibo_request_latency_seconds_bucket{{le=\"{bound}\"}} {cumulative}"
+                )
+                .unwrap();
+            });
+        writeln!(s, "kibo_request_latency_seconds_sum {}", state.latency_sum).unwrap();
+        writeln!(
+            s,
+            "kibo_request_latency_seconds_count {}",
+            state.latency_count
+        )
+        .unwrap();
+
+        write_header(
+            &mut s,
+            "kibo_response_bytes_total",
+            "counter",
+            "Bytes served before compression by route",
+        );
+        state.routes.iter().for_each(|(route, metrics)| {
+            writeln!(
+                s,
+                "kibo_response_bytes_total{{route=\"{}\"}} {}",
+                escape(route),
+                metrics.bytes
+            )
+            .unwrap();
+        });
+
+        [
+            (
+                "kibo_cache_hits_total",
+                "Responses built from the in memory cache by route",
+                (|metrics| metrics.cache_hits) as fn(&RouteMetrics) -> u64,
+            ),
+            (
+                "kibo_cache_misses_total",
+                "Responses that needed to read the disk by route",
+                |metrics| metrics.cache_misses,
+            ),
+        ]
+        .into_iter()
+        .for_each(|(name, help, get_value)| {
+            // Each family's samples need to follow its own header
+            write_header(&mut s, name, "counter", help);
+            state
+                .routes
+                .iter()
+                .filter(|(_, metrics)| metrics.cache_hits + metrics.cache_misses != 0)
+                .for_each(|(route, metrics)| {
+                    writeln!(
+                        s,
+                        "{name}{{route=\"{}\"}} {}",
+                        escape(route),
+                        get_value(metrics)
+                    )
+                    .unwrap();
+                });
+        });
+
+        s
+    }
+}
+
+fn write_header(s: &mut String, name: &str, kind: &str, help: &str) {
+    writeln!(s, "# HELP {name} {help}").unwrap();
+    writeln!(s, "# TYPE {name} {kind}").unwrap();
+}
+
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub async fn metrics_middleware(
+    State(app_state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let start = Instant::now();
+
+    let path = request.uri().path().to_owned();
+    let matched_path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched_path| matched_path.as_str().to_owned());
+
+    let response = next.run(request).await;
+
+    let status = response.status();
+
+    // Only successful api calls get their own route to keep the number of labels bounded
+    let route = if path.starts_with("/api/") && status.is_success() {
+        path.split('.').next().unwrap().to_owned()
+    } else {
+        matched_path.unwrap_or_else(|| "unmatched".to_string())
+    };
+
+    app_state.metrics.record(
+        route,
+        status.as_u16(),
+        start.elapsed(),
+        response.body().size_hint().exact().unwrap_or_default(),
+        response.extensions().get::<CacheStatus>().copied(),
+    );
+
+    response
+}
+
+pub async fn metrics_handler(State(app_state): State<AppState>) -> Response {
+    let mut response = app_state.metrics.render().into_response();
+
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        "text/plain; version=0.0.4".parse().unwrap(),
+    );
+
+    response
+}
@@ -0,0 +1,191 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderMap, Uri},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+
+use crate::{header_map::HeaderMapUtils, AppState};
+
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+const DEFAULT_BURST: f64 = 100.0;
+const ALL_DATE_WEIGHT: f64 = 10.0;
+const ALL_HEIGHT_WEIGHT: f64 = 50.0;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Client {
+    Ip(IpAddr),
+    ApiKey(String),
+}
+
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+    /// When the bucket will be refilled, it can then be forgotten
+    full_at: Instant,
+}
+
+/// Token buckets, one per IP or API key
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<Client, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Returns how long to wait before retrying if there aren't enough tokens left
+    fn take(&self, client: Client, rate: f64, burst: f64, weight: f64) -> Result<(), Duration> {
+        let now = Instant::now();
+
+        // A request heavier than the bucket would never go through
+        let weight = weight.min(burst);
+
+        let mut buckets = self.buckets.lock().unwrap();
+
+        let bucket = buckets.entry(client).or_insert(Bucket {
+            tokens: burst,
+            updated_at: now,
+            full_at: now,
+        });
+
+        bucket.tokens =
+            (bucket.tokens + now.duration_since(bucket.updated_at).as_secs_f64() * rate).min(burst);
+        bucket.updated_at = now;
+
+        let result = if bucket.tokens >= weight {
+            bucket.tokens -= weight;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((weight - bucket.tokens) / rate))
+        };
+
+        bucket.full_at = now + Duration::from_secs_f64((burst - bucket.tokens) / rate);
+
+        result
+    }
+
+    /// Forgets the buckets that are full again, a new one would be identical
+    fn prune(&self) {
+        let now = Instant::now();
+
+        self.buckets
+            .lock()
+            .unwrap()
+            .retain(|_, bucket| bucket.full_at > now);
+    }
+
+    pub fn prune_periodically(self: &Arc<Self>) {
+        let rate_limiter = self.clone();
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+
+            loop {
+                interval.tick().await;
+                rate_limiter.prune();
+            }
+        });
+    }
+}
+
+/// Optional, only active if `--rate-limit` or `--api-keys` are set
+pub async fn rate_limit_middleware(
+    State(app_state): State<AppState>,
+    ConnectInfo(address): ConnectInfo<SocketAddr>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let config = &app_state.config;
+
+    let api_key = request
+        .headers()
+        .get(API_KEY_HEADER)
+        .and_then(|api_key| api_key.to_str().ok())
+        .map(|api_key| api_key.to_owned());
+
+    let (client, rate) = if let Some(api_key) = api_key.filter(|_| config.api_keys.is_some()) {
+        if !config.api_keys().contains(&api_key) {
+            return error_response(StatusCode::UNAUTHORIZED, "Unknown API key".to_string());
+        }
+
+        (Client::ApiKey(api_key), config.api_key_rate_limit)
+    } else {
+        (
+            Client::Ip(client_ip(address, request.headers())),
+            config.rate_limit,
+        )
+    };
+
+    if let Some(rate) = rate {
+        let burst = config.rate_limit_burst.unwrap_or(DEFAULT_BURST);
+
+        if let Err(retry_after) =
+            app_state
+                .rate_limiter
+                .take(client, rate, burst, request_weight(request.uri()))
+        {
+            let mut response = error_response(
+                StatusCode::TOO_MANY_REQUESTS,
+                "Too many requests".to_string(),
+            );
+
+            response.headers_mut().insert(
+                header::RETRY_AFTER,
+                retry_after.as_secs().max(1).to_string().parse().unwrap(),
+            );
+
+            return response;
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Fetching every chunk of a dataset costs more than a single one
+fn request_weight(uri: &Uri) -> f64 {
+    let all = uri
+        .query()
+        .is_some_and(|query| query.split('&').any(|param| param == "all=true"));
+
+    if !all {
+        1.0
+    } else if uri.path().starts_with("/api/height-to-") {
+        ALL_HEIGHT_WEIGHT
+    } else {
+        ALL_DATE_WEIGHT
+    }
+}
+
+/// Behind a local proxy (cloudflared, nginx, ...) the real ip is in the headers
+fn client_ip(address: SocketAddr, headers: &HeaderMap) -> IpAddr {
+    let ip = address.ip();
+
+    if !ip.is_loopback() {
+        return ip;
+    }
+
+    headers
+        .get("cf-connecting-ip")
+        .or_else(|| headers.get("x-forwarded-for"))
+        .and_then(|forwarded| forwarded.to_str().ok())
+        .and_then(|forwarded| forwarded.split(',').next())
+        .and_then(|forwarded| forwarded.trim().parse().ok())
+        .unwrap_or(ip)
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    let mut response = (status, message).into_response();
+
+    response.headers_mut().insert_cors();
+
+    response
+}
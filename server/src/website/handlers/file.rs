@@ -5,27 +5,31 @@ use std::{
 
 use axum::{
     body::Body,
-    extract,
+    extract::{self, State},
     http::HeaderMap,
     response::{IntoResponse, Response},
 };
 use parser::log;
 use reqwest::StatusCode;
 
-use crate::header_map::HeaderMapUtils;
+use crate::{header_map::HeaderMapUtils, AppState};
 
 use super::minify_js;
 
-const WEBSITE_PATH: &str = "../website/";
+pub async fn file_handler(
+    headers: HeaderMap,
+    State(app_state): State<AppState>,
+    path: extract::Path<String>,
+) -> Response {
+    let website_path = app_state.config.website_path();
 
-pub async fn file_handler(headers: HeaderMap, path: extract::Path<String>) -> Response {
     let mut path = path.0.replace("..", "").replace("\\", "");
 
     if path.ends_with("Cargo.toml") {
         path = "../server/Cargo.toml".to_owned();
     }
 
-    let mut path = str_to_path(&path);
+    let mut path = str_to_path(&website_path, &path);
 
     if !path.exists() {
         if path.extension().is_some() {
@@ -39,19 +43,29 @@ pub async fn file_handler(headers: HeaderMap, path: extract::Path<String>) -> Re
 
             return response;
         } else {
-            path = str_to_path("index.html");
+            path = str_to_path(&website_path, "index.html");
         }
     }
 
-    path_to_response(headers, &path)
+    path_to_response(headers, &website_path, &path)
 }
 
-pub async fn index_handler(headers: HeaderMap) -> Response {
-    path_to_response(headers, &str_to_path("index.html"))
+pub async fn index_handler(headers: HeaderMap, State(app_state): State<AppState>) -> Response {
+    let website_path = app_state.config.website_path();
+
+    path_to_response(
+        headers,
+        &website_path,
+        &str_to_path(&website_path, "index.html"),
+    )
 }
 
-fn path_to_response(headers: HeaderMap, path: &Path) -> Response {
-    log(&path.to_str().unwrap().replace(WEBSITE_PATH, ""));
+fn path_to_response(headers: HeaderMap, website_path: &Path, path: &Path) -> Response {
+    log(path
+        .strip_prefix(website_path)
+        .unwrap_or(path)
+        .to_str()
+        .unwrap());
 
     let (date, response) = headers.check_if_modified_since(path).unwrap();
 
@@ -111,6 +125,6 @@ fn path_to_response(headers: HeaderMap, path: &Path) -> Response {
     response
 }
 
-fn str_to_path(path: &str) -> PathBuf {
-    PathBuf::from(&format!("{WEBSITE_PATH}{path}"))
+fn str_to_path(website_path: &Path, path: &str) -> PathBuf {
+    PathBuf::from(&format!("{}/{path}", website_path.to_str().unwrap()))
 }